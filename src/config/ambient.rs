@@ -0,0 +1,59 @@
+use super::hittable::*;
+use super::vec3::*;
+
+// Variation de l'ambiant selon l'orientation de la surface (ciel en haut, sol en bas)
+#[derive(Debug, Clone, Copy)]
+pub struct Hemisphere {
+    pub sky_color: Vec3,
+    pub ground_color: Vec3,
+    pub up: Vec3,
+}
+
+// Lumière ambiante de la scène, ajoutée une seule fois par point quel que soit le nombre de lumières
+#[derive(Debug, Clone, Copy)]
+pub struct Ambient {
+    pub color: Vec3,
+    pub intensity: f64,
+    pub hemisphere: Option<Hemisphere>,
+}
+
+impl Default for Ambient {
+    fn default() -> Self {
+        Ambient::new(Vec3::new(1.0, 1.0, 1.0), 0.1)
+    }
+}
+
+impl Ambient {
+    pub fn new(color: Vec3, intensity: f64) -> Self {
+        Ambient {
+            color,
+            intensity,
+            hemisphere: None,
+        }
+    }
+
+    pub fn with_hemisphere(mut self, sky_color: Vec3, ground_color: Vec3, up: Vec3) -> Self {
+        self.hemisphere = Some(Hemisphere {
+            sky_color,
+            ground_color,
+            up: up.normalize(),
+        });
+        self
+    }
+
+    // Couleur ambiante reçue par une surface de normale donnée
+    pub fn radiance(&self, normal: &Vec3) -> Vec3 {
+        let color = match &self.hemisphere {
+            Some(hemisphere) => {
+                let t = 0.5 * (normal.dot(&hemisphere.up) + 1.0);
+                hemisphere.ground_color * (1.0 - t) + hemisphere.sky_color * t
+            }
+            None => self.color,
+        };
+        color * self.intensity
+    }
+
+    pub fn contribution(&self, hit_record: &HitRecord) -> Vec3 {
+        hit_record.color * self.radiance(&hit_record.normal)
+    }
+}
@@ -0,0 +1,296 @@
+use super::camera::Shutter;
+use super::vec3::*;
+use std::f64::consts::PI;
+
+// Façon de passer d'une clé à la suivante
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    Linear,
+    CatmullRom, // Courbe lisse passant par toutes les clés
+    Bezier,     // Courbe cubique guidée par les poignées des clés (automatiques si absentes)
+}
+
+// Valeur animable : il suffit de savoir additionner et mettre à l'échelle
+pub trait Animatable: Copy {
+    fn add(&self, other: &Self) -> Self;
+    fn scale(&self, factor: f64) -> Self;
+}
+
+impl Animatable for f64 {
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        self * factor
+    }
+}
+
+impl Animatable for Vec3 {
+    fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    fn scale(&self, factor: f64) -> Self {
+        *self * factor
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframe<T> {
+    pub time: f64,
+    pub value: T,
+    // Poignées de Bézier (points de contrôle avant et après la clé)
+    pub in_handle: Option<T>,
+    pub out_handle: Option<T>,
+}
+
+// Suite de clés d'une valeur au cours du temps
+#[derive(Debug, Clone)]
+pub struct Track<T> {
+    pub keys: Vec<Keyframe<T>>,
+    pub interpolation: Interpolation,
+}
+
+impl<T: Animatable> Track<T> {
+    pub fn new(interpolation: Interpolation) -> Self {
+        Track {
+            keys: Vec::new(),
+            interpolation,
+        }
+    }
+
+    // Valeur fixe dans le temps
+    pub fn constant(value: T) -> Self {
+        let mut track = Track::new(Interpolation::Linear);
+        track.add_key(0.0, value);
+        track
+    }
+
+    pub fn add_key(&mut self, time: f64, value: T) -> &mut Self {
+        self.insert(Keyframe {
+            time,
+            value,
+            in_handle: None,
+            out_handle: None,
+        })
+    }
+
+    pub fn add_key_with_handles(
+        &mut self,
+        time: f64,
+        value: T,
+        in_handle: T,
+        out_handle: T,
+    ) -> &mut Self {
+        self.insert(Keyframe {
+            time,
+            value,
+            in_handle: Some(in_handle),
+            out_handle: Some(out_handle),
+        })
+    }
+
+    fn insert(&mut self, key: Keyframe<T>) -> &mut Self {
+        let index = self.keys.partition_point(|k| k.time <= key.time);
+        self.keys.insert(index, key);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    // Valeur à l'instant donné ; constante avant la première et après la dernière clé
+    pub fn evaluate(&self, time: f64) -> Option<T> {
+        let first = self.keys.first()?;
+        let last = self.keys.last()?;
+        if time <= first.time {
+            return Some(first.value);
+        }
+        if time >= last.time {
+            return Some(last.value);
+        }
+
+        let i = self.keys.partition_point(|k| k.time <= time) - 1;
+        let (k1, k2) = (&self.keys[i], &self.keys[i + 1]);
+        let t = (time - k1.time) / (k2.time - k1.time);
+        // Clés voisines, dupliquées aux extrémités
+        let k0 = &self.keys[i.saturating_sub(1)];
+        let k3 = &self.keys[(i + 2).min(self.keys.len() - 1)];
+        let (p0, p1, p2, p3) = (k0.value, k1.value, k2.value, k3.value);
+
+        let value = match self.interpolation {
+            Interpolation::Linear => p1.scale(1.0 - t).add(&p2.scale(t)),
+            Interpolation::CatmullRom => {
+                let (t2, t3) = (t * t, t * t * t);
+                p0.scale(-0.5 * t3 + t2 - 0.5 * t)
+                    .add(&p1.scale(1.5 * t3 - 2.5 * t2 + 1.0))
+                    .add(&p2.scale(-1.5 * t3 + 2.0 * t2 + 0.5 * t))
+                    .add(&p3.scale(0.5 * t3 - 0.5 * t2))
+            }
+            Interpolation::Bezier => {
+                // Poignées automatiques : tangentes de Catmull-Rom
+                let c1 = k1
+                    .out_handle
+                    .unwrap_or_else(|| p1.add(&p2.add(&p0.scale(-1.0)).scale(1.0 / 6.0)));
+                let c2 = k2
+                    .in_handle
+                    .unwrap_or_else(|| p2.add(&p3.add(&p1.scale(-1.0)).scale(-1.0 / 6.0)));
+                let s = 1.0 - t;
+                p1.scale(s * s * s)
+                    .add(&c1.scale(3.0 * s * s * t))
+                    .add(&c2.scale(3.0 * s * t * t))
+                    .add(&p2.scale(t * t * t))
+            }
+        };
+        Some(value)
+    }
+}
+
+// Position et orientation de la caméra à un instant donné
+#[derive(Debug, Clone, Copy)]
+pub struct CameraPose {
+    pub position: Vec3,
+    pub look_at: Vec3,
+    pub up: Vec3,
+    pub fov: f64, // Degrés
+}
+
+// Trajectoire de caméra définie par des clés
+#[derive(Debug, Clone)]
+pub struct CameraPath {
+    pub position: Track<Vec3>,
+    pub look_at: Track<Vec3>,
+    pub up: Track<Vec3>,
+    pub fov: Track<f64>,
+}
+
+impl CameraPath {
+    pub fn new(interpolation: Interpolation) -> Self {
+        CameraPath {
+            position: Track::new(interpolation),
+            look_at: Track::new(interpolation),
+            up: Track::new(interpolation),
+            fov: Track::new(interpolation),
+        }
+    }
+
+    pub fn add_key(&mut self, time: f64, pose: CameraPose) -> &mut Self {
+        self.position.add_key(time, pose.position);
+        self.look_at.add_key(time, pose.look_at);
+        self.up.add_key(time, pose.up);
+        self.fov.add_key(time, pose.fov);
+        self
+    }
+
+    // Tour complet autour d'une cible (platine tournante), en partant de `start` sur la durée donnée
+    pub fn turntable(target: Vec3, start: Vec3, fov: f64, duration: f64) -> Self {
+        const STEPS: usize = 36;
+        let offset = start - target;
+        let radius = (offset.x * offset.x + offset.z * offset.z).sqrt();
+        let start_angle = offset.z.atan2(offset.x);
+
+        let mut path = CameraPath::new(Interpolation::CatmullRom);
+        for step in 0..=STEPS {
+            let fraction = step as f64 / STEPS as f64;
+            let angle = start_angle + 2.0 * PI * fraction;
+            let position = Vec3::new(
+                target.x + radius * angle.cos(),
+                start.y,
+                target.z + radius * angle.sin(),
+            );
+            path.add_key(
+                fraction * duration,
+                CameraPose {
+                    position,
+                    look_at: target,
+                    up: Vec3::new(0.0, 1.0, 0.0),
+                    fov,
+                },
+            );
+        }
+        path
+    }
+
+    pub fn evaluate(&self, time: f64) -> Option<CameraPose> {
+        Some(CameraPose {
+            position: self.position.evaluate(time)?,
+            look_at: self.look_at.evaluate(time)?,
+            up: self.up.evaluate(time)?.normalize(),
+            fov: self.fov.evaluate(time)?,
+        })
+    }
+}
+
+// Variation d'une lumière de la scène (désignée par son indice) au cours du temps
+#[derive(Debug, Clone)]
+pub struct LightAnimation {
+    pub light: usize,
+    pub position: Option<Track<Vec3>>,
+    pub intensity: Option<Track<f64>>,
+    pub color: Option<Track<Vec3>>,
+}
+
+impl LightAnimation {
+    pub fn new(light: usize) -> Self {
+        LightAnimation {
+            light,
+            position: None,
+            intensity: None,
+            color: None,
+        }
+    }
+}
+
+// Plage d'images à rendre et cadence
+#[derive(Debug, Clone, Copy)]
+pub struct Timeline {
+    pub fps: f64,
+    pub start_frame: usize,
+    pub end_frame: usize, // Incluse
+    pub shutter_angle: f64, // Degrés : 180 ouvre l'obturateur pendant la moitié de chaque image, 0 sans flou
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Timeline::new(24.0, 1, 1)
+    }
+}
+
+impl Timeline {
+    pub fn new(fps: f64, start_frame: usize, end_frame: usize) -> Self {
+        Timeline {
+            fps,
+            start_frame,
+            end_frame: end_frame.max(start_frame),
+            shutter_angle: 0.0,
+        }
+    }
+
+    pub fn with_shutter_angle(mut self, degrees: f64) -> Self {
+        self.shutter_angle = degrees.clamp(0.0, 360.0);
+        self
+    }
+
+    pub fn frames(&self) -> std::ops::RangeInclusive<usize> {
+        self.start_frame..=self.end_frame
+    }
+
+    // Instant (secondes) d'une image : l'image 1 est à t = 0 quelle que soit la plage rendue,
+    // pour qu'une séquence découpée en plusieurs rendus reste continue
+    pub fn time(&self, frame: usize) -> f64 {
+        (frame as f64 - 1.0) / self.fps
+    }
+
+    // Obturateur d'une image : il s'ouvre à l'instant de l'image
+    pub fn shutter(&self, frame: usize) -> Shutter {
+        let open = self.time(frame);
+        Shutter::new(open, open + self.shutter_angle / 360.0 / self.fps)
+    }
+
+    // Durée couverte par la plage d'images
+    pub fn duration(&self) -> f64 {
+        (self.end_frame - self.start_frame + 1) as f64 / self.fps
+    }
+}
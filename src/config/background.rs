@@ -0,0 +1,41 @@
+use super::vec3::*;
+
+// Direction échantillonnée sur l'environnement, avec sa luminance et sa densité (par angle solide)
+#[derive(Debug, Clone, Copy)]
+pub struct BackgroundSample {
+    pub direction: Vec3,
+    pub radiance: Vec3,
+    pub pdf: f64,
+}
+
+// Ce que voit un rayon qui ne touche aucun objet
+pub trait Background {
+    fn radiance(&self, direction: &Vec3) -> Vec3;
+
+    // Échantillonnage pour l'éclairage : None si le fond n'éclaire pas la scène
+    fn sample(&self, _u: f64, _v: f64) -> Option<BackgroundSample> {
+        None
+    }
+}
+
+// Dégradé selon la hauteur de la direction (fond historique) : `low` vers le bas, `high` vers le haut
+pub struct Gradient {
+    pub low: Vec3,
+    pub high: Vec3,
+}
+
+impl Default for Gradient {
+    fn default() -> Self {
+        Gradient {
+            low: Vec3::new(0.5, 0.7, 1.0),
+            high: Vec3::new(1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl Background for Gradient {
+    fn radiance(&self, direction: &Vec3) -> Vec3 {
+        let t = 0.5 * (direction.y + 1.0);
+        self.low * (1.0 - t) + self.high * t
+    }
+}
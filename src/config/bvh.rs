@@ -0,0 +1,128 @@
+use super::aabb::*;
+use super::hittable::*;
+use super::ray::*;
+
+// Nombre maximal d'objets dans une feuille
+const LEAF_SIZE: usize = 2;
+
+enum Node {
+    Leaf {
+        bounds: Aabb,
+        objects: Vec<usize>,
+    },
+    Branch {
+        bounds: Aabb,
+        left: usize,
+        right: usize,
+    },
+}
+
+// Hiérarchie de volumes englobants sur les indices des objets du World. Les boîtes couvrent tout
+// l'intervalle de temps donné à la construction, les objets en mouvement y restent donc contenus
+pub struct Bvh {
+    nodes: Vec<Node>,
+    pub time0: f64,
+    pub time1: f64,
+}
+
+impl Bvh {
+    // `objects` : indices des objets et leur boîte sur [time0, time1]
+    pub fn new(objects: Vec<(usize, Aabb)>, time0: f64, time1: f64) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            time0,
+            time1,
+        };
+        if !objects.is_empty() {
+            bvh.build(objects);
+        }
+        bvh
+    }
+
+    // Construction récursive, coupe au milieu des centres le long de l'axe le plus étendu ;
+    // renvoie l'indice du nœud créé
+    fn build(&mut self, mut objects: Vec<(usize, Aabb)>) -> usize {
+        let bounds = objects
+            .iter()
+            .skip(1)
+            .fold(objects[0].1, |bounds, (_, aabb)| bounds.union(aabb));
+        if objects.len() <= LEAF_SIZE {
+            self.nodes.push(Node::Leaf {
+                bounds,
+                objects: objects.iter().map(|(index, _)| *index).collect(),
+            });
+            return self.nodes.len() - 1;
+        }
+
+        let centroids = objects
+            .iter()
+            .map(|(_, aabb)| aabb.centroid())
+            .collect::<Vec<_>>();
+        let axis = Aabb::from_points(&centroids).map_or(0, |c| c.longest_axis());
+        objects.sort_by(|a, b| {
+            axis_value(&a.1.centroid(), axis).total_cmp(&axis_value(&b.1.centroid(), axis))
+        });
+        let right_objects = objects.split_off(objects.len() / 2);
+
+        // Le nœud parent est réservé avant ses enfants pour que la racine reste en 0
+        let index = self.nodes.len();
+        self.nodes.push(Node::Leaf {
+            bounds,
+            objects: Vec::new(),
+        });
+        let left = self.build(objects);
+        let right = self.build(right_objects);
+        self.nodes[index] = Node::Branch {
+            bounds,
+            left,
+            right,
+        };
+        index
+    }
+
+    // Renvoie aussi l'indice de l'objet touché
+    pub fn hit(
+        &self,
+        objects: &[Box<dyn Hittable>],
+        ray: &Ray,
+        t_min: f64,
+        t_max: f64,
+    ) -> Option<(usize, HitRecord)> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let mut closest_hit: Option<(usize, HitRecord)> = None;
+        let mut closest_t = t_max;
+        let mut stack = vec![0];
+
+        while let Some(node) = stack.pop() {
+            match &self.nodes[node] {
+                Node::Leaf {
+                    bounds,
+                    objects: indices,
+                } => {
+                    if !bounds.hit(ray, t_min, closest_t) {
+                        continue;
+                    }
+                    for &index in indices {
+                        if let Some(hit_record) = objects[index].hit(ray, t_min, closest_t) {
+                            closest_t = hit_record.t;
+                            closest_hit = Some((index, hit_record));
+                        }
+                    }
+                }
+                Node::Branch {
+                    bounds,
+                    left,
+                    right,
+                } => {
+                    if bounds.hit(ray, t_min, closest_t) {
+                        stack.push(*right);
+                        stack.push(*left);
+                    }
+                }
+            }
+        }
+        closest_hit
+    }
+}
@@ -1,60 +1,375 @@
 use std::f64::consts::PI;
 
+use super::animation::*;
 use super::ray::*;
 use super::vec3::*;
+use super::world::World;
+use crate::projection::cylindrical::*;
+use crate::projection::equirectangular::*;
+use crate::projection::fisheye::*;
+use crate::projection::orthographic::*;
+
+// Forme de l'ouverture de l'objectif, visible dans le bokeh des zones floues
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApertureShape {
+    Circle,
+    // Polygone régulier (nombre de lamelles du diaphragme), rotation en radians
+    Polygon { blades: u32, rotation: f64 },
+}
+
+// Intervalle d'ouverture de l'obturateur (secondes) : chaque rayon part à un instant tiré dans cet intervalle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shutter {
+    pub open: f64,
+    pub close: f64,
+}
+
+impl Default for Shutter {
+    fn default() -> Self {
+        Shutter::instant(0.0)
+    }
+}
+
+impl Shutter {
+    pub fn new(open: f64, close: f64) -> Self {
+        Shutter {
+            open,
+            close: close.max(open),
+        }
+    }
+
+    // Obturateur infiniment bref : pas de flou de mouvement
+    pub fn instant(time: f64) -> Self {
+        Shutter::new(time, time)
+    }
+
+    // Instant correspondant à s dans [0, 1[
+    pub fn time(&self, s: f64) -> f64 {
+        self.open + (self.close - self.open) * s
+    }
+}
+
+// Générateur des rayons primaires ; (u, v) sont les coordonnées normalisées dans l'image
+// et (lens_u, lens_v) dans [0, 1[² choisissent le point sur l'objectif (ignorés sans objectif)
+pub trait Camera {
+    // None pour les pixels hors du champ de la projection (coins d'un fisheye circulaire)
+    fn get_ray_through_lens(&self, u: f64, v: f64, lens_u: f64, lens_v: f64) -> Option<Ray>;
+
+    // Rayon passant par le centre de l'objectif ; le rendu choisit lui-même le point de l'objectif.
+    // Les caméras dont l'ouverture n'envoie pas (0.5, 0.5) au centre doivent la redéfinir
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray> {
+        self.get_ray_through_lens(u, v, 0.5, 0.5)
+    }
+
+    // Les rayons générés sont à t = 0 ; le rendu leur attribue un instant dans cet intervalle
+    fn shutter(&self) -> Shutter {
+        Shutter::default()
+    }
+}
+
+// Caméra dont l'obturateur reste ouvert pendant un intervalle (flou de mouvement)
+pub struct ShutterCamera {
+    pub camera: Box<dyn Camera>,
+    pub shutter: Shutter,
+}
+
+impl Camera for ShutterCamera {
+    fn get_ray_through_lens(&self, u: f64, v: f64, lens_u: f64, lens_v: f64) -> Option<Ray> {
+        self.camera.get_ray_through_lens(u, v, lens_u, lens_v)
+    }
+
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray> {
+        self.camera.get_ray(u, v)
+    }
+
+    fn shutter(&self) -> Shutter {
+        self.shutter
+    }
+}
+
+// Repère de la caméra : u vers la droite, v vers le haut, w vers l'arrière (on regarde vers -w)
+#[derive(Debug, Clone, Copy)]
+pub struct CameraBasis {
+    pub origin: Vec3,
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl CameraBasis {
+    pub fn new(lookfrom: Vec3, lookat: Vec3, vup: Vec3) -> Self {
+        let w = (lookfrom - lookat).normalize();
+        let u = vup.cross(&w).normalize();
+        let v = w.cross(&u);
+        CameraBasis {
+            origin: lookfrom,
+            u,
+            v,
+            w,
+        }
+    }
+
+    // Direction exprimée dans le repère caméra (x droite, y haut, z devant) -> monde
+    pub fn to_world(&self, local: &Vec3) -> Vec3 {
+        self.u * local.x + self.v * local.y - self.w * local.z
+    }
+}
+
+// Caméra perspective (sténopé ou objectif mince)
 #[derive(Debug)]
-pub struct Camera {
+pub struct PerspectiveCamera {
     pub origin: Vec3,
     pub lower_left_corner: Vec3,
     pub horizontal: Vec3,
     pub vertical: Vec3,
+    // Base orthonormée de la caméra (u vers la droite, v vers le haut, w vers l'arrière)
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+    half_width: f64,
+    half_height: f64,
+    // Objectif mince : rayon de l'ouverture (0 = sténopé) et distance de mise au point
+    pub lens_radius: f64,
+    pub focus_distance: f64,
+    pub aperture_shape: ApertureShape,
 }
 
-impl Camera {
+impl PerspectiveCamera {
     pub fn new(lookfrom: Vec3, lookat: Vec3, vup: Vec3, vfov: f64, aspect: f64) -> Self {
         let theta = vfov * std::f64::consts::PI / 180.0;
         let half_height = (theta / 2.0).tan();
         let half_width = aspect * half_height;
 
-        let w = lookfrom.sub(&lookat).normalize();
-        let u = vup.cross(&w).normalize();
-        let v = w.cross(&u);
+        let CameraBasis { u, v, w, .. } = CameraBasis::new(lookfrom, lookat, vup);
 
-        Camera {
+        let mut camera = PerspectiveCamera {
             origin: lookfrom,
-            lower_left_corner: lookfrom
-                .sub(&u.mul(half_width))
-                .sub(&v.mul(half_height))
-                .sub(&w),
-            horizontal: u.mul(2.0 * half_width),
-            vertical: v.mul(2.0 * half_height),
+            lower_left_corner: lookfrom,
+            horizontal: u,
+            vertical: v,
+            u,
+            v,
+            w,
+            half_width,
+            half_height,
+            lens_radius: 0.0,
+            focus_distance: 1.0,
+            aperture_shape: ApertureShape::Circle,
+        };
+        camera.update_viewport();
+        camera
+    }
+
+    // Ouverture (diamètre) et distance du plan net
+    pub fn with_focus(mut self, aperture: f64, focus_distance: f64) -> Self {
+        self.lens_radius = aperture / 2.0;
+        self.focus_distance = focus_distance;
+        self.update_viewport();
+        self
+    }
+
+    pub fn with_aperture_shape(mut self, shape: ApertureShape) -> Self {
+        self.aperture_shape = shape;
+        self
+    }
+
+    // Mise au point automatique sur l'objet visible en (s, t) ; renvoie la distance retenue
+    pub fn autofocus(&mut self, world: &World, s: f64, t: f64) -> Option<f64> {
+        let ray = self.get_ray(s, t)?;
+        let hit = world.hit(&ray, 0.001, f64::INFINITY)?;
+        // Distance mesurée le long de l'axe de visée pour que le plan net passe par le point
+        let distance = (hit.point - self.origin).dot(&-self.w);
+        if distance <= 0.0 {
+            return None;
+        }
+        self.focus_distance = distance;
+        self.update_viewport();
+        Some(distance)
+    }
+
+    // Le plan image est placé à la distance de mise au point
+    fn update_viewport(&mut self) {
+        let focus = self.focus_distance;
+        self.lower_left_corner = self.origin
+            - self.u * (self.half_width * focus)
+            - self.v * (self.half_height * focus)
+            - self.w * focus;
+        self.horizontal = self.u * (2.0 * self.half_width * focus);
+        self.vertical = self.v * (2.0 * self.half_height * focus);
+    }
+
+    // Rayon vers le point (u, v) du plan net depuis le point (dx, dy) de l'objectif (rayon 1)
+    fn ray_from_lens(&self, u: f64, v: f64, dx: f64, dy: f64) -> Ray {
+        let target = self.lower_left_corner + self.horizontal * u + self.vertical * v;
+        let origin =
+            self.origin + self.u * (dx * self.lens_radius) + self.v * (dy * self.lens_radius);
+        Ray::new(origin, (target - origin).normalize())
+    }
+
+    // Point de l'ouverture de rayon 1 ; (0.5, 0.5) ne tombe au centre que pour l'ouverture circulaire
+    fn sample_aperture(&self, a: f64, b: f64) -> (f64, f64) {
+        match self.aperture_shape {
+            ApertureShape::Circle => concentric_disk(a, b),
+            ApertureShape::Polygon { blades, rotation } => {
+                let blades = blades.max(3) as f64;
+                // a choisit le secteur triangulaire puis sert de coordonnée dans celui-ci
+                let scaled = a * blades;
+                let sector = scaled.floor().min(blades - 1.0);
+                let a = scaled - sector;
+                let angle0 = rotation + 2.0 * PI * sector / blades;
+                let angle1 = angle0 + 2.0 * PI / blades;
+                let sa = a.sqrt();
+                let (w0, w1) = (sa * (1.0 - b), sa * b);
+                (
+                    w0 * angle0.cos() + w1 * angle1.cos(),
+                    w0 * angle0.sin() + w1 * angle1.sin(),
+                )
+            }
         }
     }
+}
 
-    pub fn get_ray(&self, u: f64, v: f64) -> Ray {
-        Ray::new(
-            self.origin,
-            self.lower_left_corner
-                .add(&self.horizontal.mul(u))
-                .add(&self.vertical.mul(v))
-                .sub(&self.origin)
-                .normalize(),
-        )
+impl Camera for PerspectiveCamera {
+    fn get_ray_through_lens(&self, u: f64, v: f64, lens_u: f64, lens_v: f64) -> Option<Ray> {
+        let (dx, dy) = self.sample_aperture(lens_u, lens_v);
+        Some(self.ray_from_lens(u, v, dx, dy))
     }
 
-    pub fn moves(&mut self) {
-        let radius = 1.0; // Rayon de l'orbite de la caméra
-        // let center = Vec3::new(0.0, 0.0, 0.0); // Centre de la scène
+    // Centre exact de l'objectif, quelle que soit la forme de l'ouverture
+    fn get_ray(&self, u: f64, v: f64) -> Option<Ray> {
+        Some(self.ray_from_lens(u, v, 0.0, 0.0))
+    }
+}
+
+// Répartition uniforme sur le disque unité (Shirley-Chiu), (0.5, 0.5) donne le centre
+fn concentric_disk(a: f64, b: f64) -> (f64, f64) {
+    let x = 2.0 * a - 1.0;
+    let y = 2.0 * b - 1.0;
+    if x == 0.0 && y == 0.0 {
+        return (0.0, 0.0);
+    }
+    let (r, theta) = if x.abs() > y.abs() {
+        (x, (PI / 4.0) * (y / x))
+    } else {
+        (y, PI / 2.0 - (PI / 4.0) * (x / y))
+    };
+    (r * theta.cos(), r * theta.sin())
+}
 
-        let angle_degrees = 45.0; // Angle de rotation autour de l'axe Y
-        let theta = angle_degrees * PI / 180.0; // Conversion en radians
+// Projection choisie dans les réglages de la scène
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    Perspective { vfov: f64 },
+    Orthographic { view_height: f64 },
+    Fisheye { fov: f64, mapping: FisheyeMapping },
+    Equirectangular,
+    Cylindrical { horizontal_fov: f64, vertical_fov: f64 },
+}
 
-        // Nouvelle position de la caméra en orbite autour du centre
-        let camera_x = radius * theta.cos();
-        let camera_z = radius * theta.sin();
-        let camera_position = Vec3::new(camera_x, 2.0, camera_z);
+// Réglages de la caméra de la scène, à partir desquels on construit la caméra voulue
+#[derive(Debug, Clone, Copy)]
+pub struct CameraSettings {
+    pub lookfrom: Vec3,
+    pub lookat: Vec3,
+    pub vup: Vec3,
+    pub aspect: f64,
+    // Largeur d'un pixel rapportée à sa hauteur : l'image est prévue pour être étirée d'autant
+    pub pixel_aspect: f64,
+    pub projection: Projection,
+    // Objectif mince (perspective uniquement) : ouverture et distance du plan net (None = mise au point au centre)
+    pub aperture: f64,
+    pub focus_distance: Option<f64>,
+    pub aperture_shape: ApertureShape,
+    pub shutter: Shutter,
+}
 
-        self.origin = camera_position;
-        // self.lower_left_corner = center;
+impl CameraSettings {
+    pub fn new(lookfrom: Vec3, lookat: Vec3, vup: Vec3, aspect: f64, projection: Projection) -> Self {
+        CameraSettings {
+            lookfrom,
+            lookat,
+            vup,
+            aspect,
+            pixel_aspect: 1.0,
+            projection,
+            aperture: 0.0,
+            focus_distance: None,
+            aperture_shape: ApertureShape::Circle,
+            shutter: Shutter::default(),
+        }
+    }
+
+    pub fn with_pixel_aspect(mut self, pixel_aspect: f64) -> Self {
+        self.pixel_aspect = pixel_aspect;
+        self
+    }
+
+    pub fn with_shutter(mut self, shutter: Shutter) -> Self {
+        self.shutter = shutter;
+        self
+    }
+
+    // Réglages placés à une pose d'animation (le champ de vision ne concerne que la perspective)
+    pub fn with_pose(mut self, pose: &CameraPose) -> Self {
+        self.lookfrom = pose.position;
+        self.lookat = pose.look_at;
+        self.vup = pose.up;
+        if let Projection::Perspective { vfov } = &mut self.projection {
+            *vfov = pose.fov;
+        }
+        self
+    }
+
+    pub fn build(&self, world: &World) -> Box<dyn Camera> {
+        let (lookfrom, lookat, vup) = (self.lookfrom, self.lookat, self.vup);
+        // Rapport largeur / hauteur du cadre une fois les pixels affichés à leur forme
+        let aspect = self.aspect * self.pixel_aspect;
+        let camera: Box<dyn Camera> = match self.projection {
+            Projection::Perspective { vfov } => {
+                let mut camera = PerspectiveCamera::new(lookfrom, lookat, vup, vfov, aspect)
+                    .with_aperture_shape(self.aperture_shape);
+                if self.aperture > 0.0 {
+                    camera = camera.with_focus(self.aperture, self.focus_distance.unwrap_or(1.0));
+                    if self.focus_distance.is_none() {
+                        camera.autofocus(world, 0.5, 0.5);
+                    }
+                }
+                Box::new(camera)
+            }
+            Projection::Orthographic { view_height } => Box::new(OrthographicCamera::new(
+                lookfrom,
+                lookat,
+                vup,
+                view_height,
+                aspect,
+            )),
+            Projection::Fisheye { fov, mapping } => Box::new(FisheyeCamera::new(
+                lookfrom,
+                lookat,
+                vup,
+                fov,
+                aspect,
+                mapping,
+            )),
+            Projection::Equirectangular => Box::new(EquirectangularCamera::new(lookfrom, lookat, vup)),
+            Projection::Cylindrical {
+                horizontal_fov,
+                vertical_fov,
+            } => Box::new(CylindricalCamera::new(
+                lookfrom,
+                lookat,
+                vup,
+                horizontal_fov,
+                vertical_fov,
+            )),
+        };
+        if self.shutter == Shutter::default() {
+            camera
+        } else {
+            Box::new(ShutterCamera {
+                camera,
+                shutter: self.shutter,
+            })
+        }
     }
 }
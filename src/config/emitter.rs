@@ -0,0 +1,66 @@
+use super::vec3::*;
+use super::world::World;
+
+// Point échantillonné sur un objet lumineux, avec sa densité par unité d'aire
+#[derive(Debug, Clone, Copy)]
+pub struct EmitterSample {
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub emission: Vec3,
+    pub pdf: f64,
+}
+
+// Liste des objets émissifs du monde, échantillonnés proportionnellement à leur aire
+pub struct EmitterList {
+    indices: Vec<usize>, // Indices dans `world.objects`
+    cdf: Vec<f64>,
+    total_area: f64,
+}
+
+impl EmitterList {
+    pub fn new(world: &World) -> Self {
+        let mut indices = Vec::new();
+        let mut cdf = Vec::new();
+        let mut total_area = 0.0;
+        for (index, object) in world.objects.iter().enumerate() {
+            let area = object.area();
+            if object.emission().length_squared() > 0.0 && area > 0.0 {
+                total_area += area;
+                indices.push(index);
+                cdf.push(total_area);
+            }
+        }
+        EmitterList {
+            indices,
+            cdf,
+            total_area,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    // `select` choisit l'objet, (u, v) le point sur sa surface ; tous dans [0, 1[
+    pub fn sample(&self, world: &World, select: f64, u: f64, v: f64) -> Option<EmitterSample> {
+        if self.is_empty() {
+            return None;
+        }
+        let target = select * self.total_area;
+        let slot = self.cdf.partition_point(|&c| c <= target).min(self.len() - 1);
+        let object = &world.objects[self.indices[slot]];
+        let surface = object.sample_surface(u, v)?;
+
+        // Probabilité (aire / aire totale) × densité uniforme (1 / aire) = 1 / aire totale
+        Some(EmitterSample {
+            point: surface.point,
+            normal: surface.normal,
+            emission: object.emission(),
+            pdf: 1.0 / self.total_area,
+        })
+    }
+}
@@ -0,0 +1,302 @@
+use super::background::*;
+use super::vec3::*;
+use std::f64::consts::PI;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Carte d'environnement équirectangulaire (HDR), utilisée comme fond et comme source de lumière
+pub struct EnvironmentMap {
+    width: usize,
+    height: usize,
+    pixels: Vec<Vec3>,
+    pub rotation: f64, // Rotation autour de l'axe Y (radians)
+    pub intensity: f64,
+    // Fonctions de répartition pour l'échantillonnage selon la luminance
+    marginal: Vec<f64>,         // Sur les lignes (height + 1 valeurs)
+    conditional: Vec<Vec<f64>>, // Sur les colonnes de chaque ligne (width + 1 valeurs)
+}
+
+impl EnvironmentMap {
+    pub fn new(width: usize, height: usize, pixels: Vec<Vec3>) -> io::Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(invalid_data("carte d'environnement vide"));
+        }
+        if pixels.len() != width * height {
+            return Err(invalid_data("taille de la carte incohérente"));
+        }
+
+        // Poids de chaque pixel : luminance × sin(θ) pour compenser l'étirement aux pôles
+        let mut conditional = Vec::with_capacity(height);
+        let mut row_weights = Vec::with_capacity(height);
+        for j in 0..height {
+            let sin_theta = (PI * (j as f64 + 0.5) / height as f64).sin();
+            let weights: Vec<f64> = (0..width)
+                .map(|i| pixels[j * width + i].luminance().max(0.0) * sin_theta)
+                .collect();
+            row_weights.push(weights.iter().sum::<f64>());
+            conditional.push(cumulative(&weights));
+        }
+
+        Ok(EnvironmentMap {
+            width,
+            height,
+            pixels,
+            rotation: 0.0,
+            intensity: 1.0,
+            marginal: cumulative(&row_weights),
+            conditional,
+        })
+    }
+
+    // Charge une image .hdr (Radiance RGBE) ou .exr
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let (width, height, pixels) = match extension.as_deref() {
+            Some("hdr") => read_hdr(&fs::read(path)?)?,
+            Some("exr") => read_exr(path)?,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("format d'environnement non supporté : {}", path.display()),
+                ))
+            }
+        };
+        EnvironmentMap::new(width, height, pixels)
+    }
+
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.rotation = degrees * PI / 180.0;
+        self
+    }
+
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    fn pixel(&self, u: f64, v: f64) -> Vec3 {
+        let i = ((u * self.width as f64) as usize).min(self.width - 1);
+        let j = ((v * self.height as f64) as usize).min(self.height - 1);
+        self.pixels[j * self.width + i]
+    }
+
+    // Direction monde -> coordonnées (u, v) de la carte
+    fn direction_to_uv(&self, direction: &Vec3) -> (f64, f64) {
+        let d = direction.normalize().rotate_y(-self.rotation);
+        let phi = d.x.atan2(-d.z);
+        let theta = d.y.clamp(-1.0, 1.0).acos();
+        ((phi + PI) / (2.0 * PI), theta / PI)
+    }
+
+    // Coordonnées (u, v) de la carte -> direction monde
+    fn uv_to_direction(&self, u: f64, v: f64) -> Vec3 {
+        let phi = u * 2.0 * PI - PI;
+        let theta = v * PI;
+        let d = Vec3::new(
+            theta.sin() * phi.sin(),
+            theta.cos(),
+            -theta.sin() * phi.cos(),
+        );
+        d.rotate_y(self.rotation)
+    }
+}
+
+impl Background for EnvironmentMap {
+    fn radiance(&self, direction: &Vec3) -> Vec3 {
+        let (u, v) = self.direction_to_uv(direction);
+        self.pixel(u, v) * self.intensity
+    }
+
+    fn sample(&self, u: f64, v: f64) -> Option<BackgroundSample> {
+        if self.marginal[self.height] <= 0.0 {
+            return None;
+        }
+
+        let (row, row_pdf, map_v) = sample_cumulative(&self.marginal, u);
+        let (_, column_pdf, map_u) = sample_cumulative(&self.conditional[row], v);
+
+        let sin_theta = (map_v * PI).sin();
+        if sin_theta <= 0.0 {
+            return None;
+        }
+        // Densité sur l'image -> densité par angle solide
+        let pdf = row_pdf * column_pdf / (2.0 * PI * PI * sin_theta);
+        let direction = self.uv_to_direction(map_u, map_v);
+
+        Some(BackgroundSample {
+            direction,
+            radiance: self.pixel(map_u, map_v) * self.intensity,
+            pdf,
+        })
+    }
+}
+
+// Fonction de répartition normalisée (n + 1 valeurs, de 0 à 1) ; reste à 0 si tous les poids sont nuls
+fn cumulative(weights: &[f64]) -> Vec<f64> {
+    let mut cdf = Vec::with_capacity(weights.len() + 1);
+    cdf.push(0.0);
+    let mut total = 0.0;
+    for w in weights {
+        total += w;
+        cdf.push(total);
+    }
+    if total > 0.0 {
+        for c in cdf.iter_mut() {
+            *c /= total;
+        }
+    }
+    cdf
+}
+
+// Inverse la fonction de répartition : (indice, densité sur [0, 1], position continue dans [0, 1])
+fn sample_cumulative(cdf: &[f64], u: f64) -> (usize, f64, f64) {
+    let n = cdf.len() - 1;
+    let index = (cdf.partition_point(|&c| c <= u) - 1).min(n - 1);
+    let width = cdf[index + 1] - cdf[index];
+    let offset = if width > 0.0 {
+        (u - cdf[index]) / width
+    } else {
+        0.5
+    };
+    (index, width * n as f64, (index as f64 + offset) / n as f64)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+// Lecture d'une image Radiance RGBE (.hdr), scanlines plates ou compressées en RLE
+fn read_hdr(data: &[u8]) -> io::Result<(usize, usize, Vec<Vec3>)> {
+    let mut pos = 0;
+    let mut next_line = || -> io::Result<String> {
+        let end = data[pos..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .ok_or_else(|| invalid_data("en-tête HDR incomplet"))?;
+        let line = String::from_utf8_lossy(&data[pos..pos + end])
+            .trim()
+            .to_string();
+        pos += end + 1;
+        Ok(line)
+    };
+
+    if !next_line()?.starts_with("#?") {
+        return Err(invalid_data("fichier HDR invalide"));
+    }
+    loop {
+        let line = next_line()?;
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with("FORMAT=") && line != "FORMAT=32-bit_rle_rgbe" {
+            return Err(invalid_data("seul le format 32-bit_rle_rgbe est supporté"));
+        }
+    }
+    let resolution = next_line()?;
+    let parts: Vec<&str> = resolution.split_whitespace().collect();
+    let (height, width) = match parts.as_slice() {
+        ["-Y", h, "+X", w] => (
+            h.parse::<usize>()
+                .map_err(|_| invalid_data("hauteur HDR invalide"))?,
+            w.parse::<usize>()
+                .map_err(|_| invalid_data("largeur HDR invalide"))?,
+        ),
+        _ => return Err(invalid_data("orientation HDR non supportée")),
+    };
+
+    let mut pixels = Vec::with_capacity(width * height);
+    let mut scanline = vec![[0u8; 4]; width];
+    for _ in 0..height {
+        pos = read_hdr_scanline(data, pos, &mut scanline)?;
+        pixels.extend(scanline.iter().map(rgbe_to_rgb));
+    }
+    Ok((width, height, pixels))
+}
+
+fn read_hdr_scanline(data: &[u8], mut pos: usize, scanline: &mut [[u8; 4]]) -> io::Result<usize> {
+    let width = scanline.len();
+    let byte = |p: usize| {
+        data.get(p)
+            .copied()
+            .ok_or_else(|| invalid_data("données HDR tronquées"))
+    };
+
+    let is_rle = (8..0x8000).contains(&width)
+        && byte(pos)? == 2
+        && byte(pos + 1)? == 2
+        && byte(pos + 2)? & 0x80 == 0;
+    if !is_rle {
+        for pixel in scanline.iter_mut() {
+            for (c, value) in pixel.iter_mut().enumerate() {
+                *value = byte(pos + c)?;
+            }
+            pos += 4;
+        }
+        return Ok(pos);
+    }
+
+    if ((byte(pos + 2)? as usize) << 8 | byte(pos + 3)? as usize) != width {
+        return Err(invalid_data("largeur de scanline HDR incohérente"));
+    }
+    pos += 4;
+    // Chaque composante est compressée séparément
+    for c in 0..4 {
+        let mut x = 0;
+        while x < width {
+            let count = byte(pos)? as usize;
+            pos += 1;
+            if count > 128 {
+                let run = count - 128;
+                let value = byte(pos)?;
+                pos += 1;
+                if x + run > width {
+                    return Err(invalid_data("scanline HDR corrompue"));
+                }
+                scanline[x..x + run].iter_mut().for_each(|p| p[c] = value);
+                x += run;
+            } else {
+                if count == 0 || x + count > width {
+                    return Err(invalid_data("scanline HDR corrompue"));
+                }
+                for k in 0..count {
+                    scanline[x + k][c] = byte(pos + k)?;
+                }
+                pos += count;
+                x += count;
+            }
+        }
+    }
+    Ok(pos)
+}
+
+fn rgbe_to_rgb(rgbe: &[u8; 4]) -> Vec3 {
+    if rgbe[3] == 0 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+    let scale = 2f64.powi(rgbe[3] as i32 - 136);
+    Vec3::new(rgbe[0] as f64, rgbe[1] as f64, rgbe[2] as f64) * scale
+}
+
+fn read_exr(path: &Path) -> io::Result<(usize, usize, Vec<Vec3>)> {
+    let image = exr::prelude::read_first_rgba_layer_from_file(
+        path,
+        |resolution, _| {
+            let size = resolution.width() * resolution.height();
+            (resolution.width(), vec![Vec3::new(0.0, 0.0, 0.0); size])
+        },
+        |(width, pixels): &mut (usize, Vec<Vec3>),
+         position,
+         (r, g, b, _a): (f32, f32, f32, f32)| {
+            pixels[position.y() * *width + position.x()] = Vec3::new(r as f64, g as f64, b as f64);
+        },
+    )
+    .map_err(|e| invalid_data(&e.to_string()))?;
+
+    let (width, pixels) = image.layer_data.channel_data.pixels;
+    Ok((width, pixels.len() / width.max(1), pixels))
+}
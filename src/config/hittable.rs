@@ -1,6 +1,9 @@
 
 use super::vec3::*;
 use super::ray::*;
+use super::material::*;
+use super::aabb::*;
+use super::validation::*;
 
 #[derive(Clone, Copy)]
 pub struct HitRecord {
@@ -8,9 +11,50 @@ pub struct HitRecord {
     pub point: Vec3,
     pub normal: Vec3,
     pub color: Vec3,
+    pub material: Material,
+}
+
+// Point tiré sur la surface d'un objet
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceSample {
+    pub point: Vec3,
+    pub normal: Vec3,
 }
 
 // Trait Hittable pour les objets rendables
 pub trait Hittable {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord>;
+
+    // Lumière émise par l'objet (nulle par défaut)
+    fn emission(&self) -> Vec3 {
+        Vec3::new(0.0, 0.0, 0.0)
+    }
+
+    // Aire de la surface, 0 pour les objets qu'on ne sait pas échantillonner (plans infinis)
+    fn area(&self) -> f64 {
+        0.0
+    }
+
+    // Point uniformément réparti sur la surface pour (u, v) dans [0, 1[²
+    fn sample_surface(&self, _u: f64, _v: f64) -> Option<SurfaceSample> {
+        None
+    }
+
+    // Boîte contenant l'objet pendant tout l'intervalle [time0, time1] ; None pour les objets infinis
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<Aabb> {
+        None
+    }
+
+    // Place l'objet à l'instant donné (secondes) ; sans effet pour un objet immobile
+    fn set_time(&mut self, _time: f64) {}
+
+    // Paramètres géométriques invalides (rayon négatif, axe nul, NaN...) qui produiraient des pixels NaN
+    fn validate(&self) -> Result<(), GeometryError> {
+        Ok(())
+    }
+
+    // Point à l'intérieur du volume délimité par l'objet ; faux pour les surfaces ouvertes (plan, triangle)
+    fn contains(&self, _point: &Vec3) -> bool {
+        false
+    }
 }
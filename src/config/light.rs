@@ -26,13 +26,22 @@
 // }
 
 use super::vec3::*;
+use super::background::*;
+use super::emitter::*;
 use super::hittable::*;
 use super::ray::Ray;
 use super::world::World;
+use std::f64::consts::PI;
 
 pub struct Light {
     pub position: Vec3,
+    // Éclairement reçu face à la lumière divisé par π : avec la BRDF lambertienne (albédo / π)
+    // commune à toutes les sources, une surface blanche éclairée de face renvoie `intensity`,
+    // comme sous un fond uniforme ou un émetteur de radiance `intensity`
     pub intensity: f64,
+    pub color: Vec3,
+    // Lumière directionnelle (soleil) : direction vers la source, la position est alors ignorée
+    pub direction: Option<Vec3>,
 }
 
 impl Light {
@@ -40,50 +49,301 @@ impl Light {
         Light {
             position,
             intensity,
+            color: Vec3::new(1.0, 1.0, 1.0),
+            direction: None,
         }
     }
+
+    pub fn directional(direction: Vec3, intensity: f64, color: Vec3) -> Self {
+        Light {
+            position: Vec3::new(0.0, 0.0, 0.0),
+            intensity,
+            color,
+            direction: Some(direction.normalize()),
+        }
+    }
+
+    pub fn with_color(mut self, color: Vec3) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Direction (normalisée) et distance vers la lumière depuis un point
+    pub fn direction_from(&self, point: &Vec3) -> (Vec3, f64) {
+        match self.direction {
+            Some(direction) => (direction, f64::INFINITY),
+            None => {
+                let to_light = self.position - *point;
+                (to_light.normalize(), to_light.length())
+            }
+        }
+    }
+}
+
+// Modèle utilisé pour le lobe spéculaire
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecularModel {
+    Phong,      // (R·V)^n
+    BlinnPhong, // (N·H)^n avec H le demi-vecteur entre L et V
 }
 
-pub fn calculate_lighting(hit_record: &HitRecord, light: &Light, world: &World) -> Vec3 {
-    // Vérification des ombres
-    let shadow_ray_direction = light.position.sub(&hit_record.point);
-    let shadow_ray_distance = shadow_ray_direction.length();
+// Paramètres de l'ombrage (communs à toutes les lumières)
+#[derive(Debug, Clone, Copy)]
+pub struct Shading {
+    pub model: SpecularModel,
+    pub specular_strength: f64,
+    pub shininess: f64,
+    // Normalise le lobe spéculaire pour qu'il conserve l'énergie quelle que soit la brillance
+    pub normalized: bool,
+    // Nombre maximal de surfaces transmissives traversées par un rayon d'ombre
+    pub max_shadow_layers: usize,
+    // Nombre de directions de l'environnement échantillonnées par point (éclairage par image)
+    pub environment_samples: usize,
+    // Nombre de points tirés sur les objets émissifs par point éclairé
+    pub emitter_samples: usize,
+}
+
+impl Default for Shading {
+    fn default() -> Self {
+        Shading {
+            model: SpecularModel::Phong,
+            specular_strength: 0.5,
+            shininess: 32.0,
+            normalized: false,
+            max_shadow_layers: 8,
+            environment_samples: 1,
+            emitter_samples: 1,
+        }
+    }
+}
+
+impl Shading {
+    pub fn blinn_phong(specular_strength: f64, shininess: f64) -> Self {
+        Shading {
+            model: SpecularModel::BlinnPhong,
+            specular_strength,
+            shininess,
+            normalized: false,
+            ..Shading::default()
+        }
+    }
+
+    pub fn normalized(mut self) -> Self {
+        self.normalized = true;
+        self
+    }
+
+    // Lobe spéculaire pour une direction de lumière et de vue données (toutes deux partant du point)
+    pub fn specular(&self, normal: &Vec3, light_direction: &Vec3, view_direction: &Vec3) -> f64 {
+        let n = self.shininess;
+        let (lobe, normalization) = match self.model {
+            SpecularModel::Phong => {
+                let reflect_direction = (-*light_direction).reflect(normal);
+                let lobe = reflect_direction.dot(view_direction).max(0.0).powf(n);
+                (lobe, (n + 2.0) / (2.0 * PI))
+            }
+            SpecularModel::BlinnPhong => {
+                let half = *light_direction + *view_direction;
+                if half.length_squared() == 0.0 {
+                    return 0.0;
+                }
+                let lobe = normal.dot(&half.normalize()).max(0.0).powf(n);
+                (lobe, (n + 8.0) / (8.0 * PI))
+            }
+        };
+
+        if self.normalized {
+            // Le facteur cosinus fait partie de l'équation du rendu pour un lobe normalisé
+            lobe * normalization * normal.dot(light_direction).max(0.0)
+        } else {
+            lobe
+        }
+    }
+}
+
+// Part de la lumière qui atteint le point : 1 sans obstacle, 0 derrière un objet opaque,
+// filtrée par les objets transparents traversés. Le rayon d'ombre part à l'instant `time`
+pub fn light_visibility(
+    hit_record: &HitRecord,
+    light: &Light,
+    world: &World,
+    shading: &Shading,
+    time: f64,
+) -> Vec3 {
+    let (light_direction, shadow_ray_distance) = light.direction_from(&hit_record.point);
     let shadow_ray = Ray {
-        origin: hit_record.point.add(&hit_record.normal.mul(0.001)), // Légère offset pour éviter l'auto-intersection
-        direction: shadow_ray_direction.normalize(),
+        origin: hit_record.point + hit_record.normal * 0.001, // Légère offset pour éviter l'auto-intersection
+        direction: light_direction,
+        time,
     };
+    shadow_transmittance(world, &shadow_ray, shadow_ray_distance, shading.max_shadow_layers)
+}
 
-    // Si un objet bloque le chemin vers la lumière, le point est dans l'ombre
-    if let Some(_shadow_hit) = world.hit(&shadow_ray, 0.001, shadow_ray_distance) {
-        return hit_record.color.mul(0.1); // Retourne uniquement la composante ambiante
+// Part de l'éclairage direct des lumières masquée au point (0 = pleine lumière, 1 = dans l'ombre),
+// chaque lumière comptant pour ce qu'elle apporterait sans obstacle
+pub fn shadow_fraction(
+    hit_record: &HitRecord,
+    lights: &[Light],
+    world: &World,
+    shading: &Shading,
+    time: f64,
+) -> f64 {
+    let mut unoccluded = 0.0;
+    let mut received = 0.0;
+    for light in lights {
+        let (light_direction, _) = light.direction_from(&hit_record.point);
+        let weight =
+            light.intensity * light.color.luminance() * hit_record.normal.dot(&light_direction).max(0.0);
+        if weight > 0.0 {
+            let visibility = light_visibility(hit_record, light, world, shading, time);
+            unoccluded += weight;
+            received += weight * visibility.luminance().clamp(0.0, 1.0);
+        }
+    }
+    if unoccluded > 0.0 {
+        1.0 - received / unoccluded
+    } else {
+        0.0
     }
+}
 
-    // Composante ambiante - lumière de base
-    let ambient_strength = 0.1;
-    let ambient = hit_record.color.mul(ambient_strength);
+// `ray` est le rayon qui a touché le point : la vue est l'opposé de sa direction, et les rayons
+// d'ombre partent au même instant que lui
+pub fn calculate_lighting(
+    hit_record: &HitRecord,
+    ray: &Ray,
+    light: &Light,
+    world: &World,
+    shading: &Shading,
+) -> Vec3 {
+    // Si un objet opaque bloque le chemin vers la lumière, elle ne contribue pas (l'ambiant est ajouté par la scène)
+    let transmittance = light_visibility(hit_record, light, world, shading, ray.time);
+    if transmittance.length_squared() == 0.0 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+    let (light_direction, _) = light.direction_from(&hit_record.point);
 
     // Composante diffuse - réflexion de la lumière sur la surface
-    let light_direction = shadow_ray_direction.normalize();
     let normal = hit_record.normal;
     let diff = normal.dot(&light_direction).max(0.0);
-    let diffuse = hit_record.color.mul(diff * light.intensity);
-
-    // Composante spéculaire - reflets brillants
-    let view_direction = hit_record.point.mul(-1.0).normalize();
-    let reflect_direction = reflect(&light_direction.mul(-1.0), &normal);
-    
-    let specular_strength = 0.5;
-    let shininess = 32.0;
-    let spec = reflect_direction.dot(&view_direction)
-        .max(0.0)
-        .powf(shininess);
-    let specular = Vec3::new(1.0, 1.0, 1.0)
-        .mul(spec * specular_strength * light.intensity);
-
-    // Combinaison des trois composantes
-    ambient.add(&diffuse).add(&specular)
+    let irradiance = light.color * (light.intensity * PI);
+    let diffuse = lambert(hit_record) * irradiance * diff;
+
+    // Composante spéculaire - reflets brillants, vus depuis l'origine du rayon (la caméra).
+    // Le lobe normalisé est une BRDF (cosinus compris) et reçoit le même éclairement que le diffus
+    let view_direction = (-ray.direction).normalize();
+    let spec = shading.specular(&normal, &light_direction, &view_direction);
+    let specular = if shading.normalized {
+        irradiance * (spec * shading.specular_strength)
+    } else {
+        light.color * (spec * shading.specular_strength * light.intensity)
+    };
+
+    // Combinaison des composantes diffuse et spéculaire, filtrées par les objets transparents traversés
+    (diffuse + specular) * transmittance
+}
+
+// Éclairage reçu du fond pour une direction choisie selon sa luminance (u, v aléatoires dans [0, 1[)
+pub fn environment_lighting(
+    hit_record: &HitRecord,
+    background: &dyn Background,
+    world: &World,
+    shading: &Shading,
+    time: f64,
+    u: f64,
+    v: f64,
+) -> Vec3 {
+    let sample = match background.sample(u, v) {
+        Some(sample) if sample.pdf > 0.0 => sample,
+        _ => return Vec3::new(0.0, 0.0, 0.0),
+    };
+    let cos_theta = hit_record.normal.dot(&sample.direction);
+    if cos_theta <= 0.0 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+
+    let shadow_ray = Ray {
+        origin: hit_record.point + hit_record.normal * 0.001,
+        direction: sample.direction,
+        time,
+    };
+    let transmittance =
+        shadow_transmittance(world, &shadow_ray, f64::INFINITY, shading.max_shadow_layers);
+
+    // Estimateur de Monte-Carlo divisé par la densité
+    lambert(hit_record) * sample.radiance * transmittance * (cos_theta / sample.pdf)
 }
 
-fn reflect(v: &Vec3, n: &Vec3) -> Vec3 {
-    v.sub(&n.mul(2.0 * v.dot(n)))
-}
\ No newline at end of file
+// Éclairage direct reçu d'un point tiré sur les objets émissifs (select, u, v aléatoires dans [0, 1[)
+pub fn emitter_lighting(
+    hit_record: &HitRecord,
+    emitters: &EmitterList,
+    world: &World,
+    shading: &Shading,
+    time: f64,
+    sample: (f64, f64, f64),
+) -> Vec3 {
+    let (select, u, v) = sample;
+    let emitter = match emitters.sample(world, select, u, v) {
+        Some(emitter) => emitter,
+        None => return Vec3::new(0.0, 0.0, 0.0),
+    };
+
+    let origin = hit_record.point + hit_record.normal * 0.001;
+    let to_emitter = emitter.point - origin;
+    let distance_squared = to_emitter.length_squared();
+    let distance = distance_squared.sqrt();
+    if distance <= 0.001 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+    let direction = to_emitter * (1.0 / distance);
+
+    // Les surfaces émissives éclairent des deux côtés
+    let cos_surface = hit_record.normal.dot(&direction);
+    let cos_emitter = emitter.normal.dot(&direction).abs();
+    if cos_surface <= 0.0 || cos_emitter <= 0.0 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+
+    // Le rayon d'ombre s'arrête juste avant l'émetteur pour ne pas le toucher lui-même
+    let shadow_ray = Ray { origin, direction, time };
+    let transmittance =
+        shadow_transmittance(world, &shadow_ray, distance - 0.001, shading.max_shadow_layers);
+
+    // Conversion de la densité par aire en densité par angle solide
+    let geometry = cos_surface * cos_emitter / (distance_squared * emitter.pdf);
+    lambert(hit_record) * emitter.emission * transmittance * geometry
+}
+
+// BRDF lambertienne (albédo / π), la même pour les lumières, le fond et les objets émissifs
+fn lambert(hit_record: &HitRecord) -> Vec3 {
+    hit_record.color / PI
+}
+
+// Lumière qui atteint la fin du rayon d'ombre : chaque surface transmissive traversée la filtre,
+// une surface opaque (ou trop de couches) l'arrête complètement
+pub fn shadow_transmittance(world: &World, shadow_ray: &Ray, distance: f64, max_layers: usize) -> Vec3 {
+    let mut transmittance = Vec3::new(1.0, 1.0, 1.0);
+    let mut ray = Ray {
+        origin: shadow_ray.origin,
+        direction: shadow_ray.direction,
+        time: shadow_ray.time,
+    };
+    let mut remaining = distance;
+    let mut layers = 0;
+
+    while let Some(shadow_hit) = world.hit(&ray, 0.001, remaining) {
+        let layer = shadow_hit.material.shadow_transmittance(&shadow_hit.point);
+        if layers == max_layers || layer.length_squared() == 0.0 {
+            return Vec3::new(0.0, 0.0, 0.0);
+        }
+        transmittance = transmittance * layer;
+        layers += 1;
+
+        // On repart du point touché pour chercher la couche suivante
+        ray.origin = shadow_hit.point;
+        remaining -= shadow_hit.t;
+    }
+
+    transmittance
+}
@@ -0,0 +1,99 @@
+use super::vec3::*;
+
+// Masque de découpe : part de la surface réellement présente en un point (1 = pleine, 0 = trou)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaMask {
+    Opaque,
+    Constant(f64),
+    // Damier 3D en coordonnées monde, une case sur deux a l'alpha donné
+    Checker { scale: f64, alpha: f64 },
+}
+
+impl AlphaMask {
+    pub fn coverage(&self, point: &Vec3) -> f64 {
+        match *self {
+            AlphaMask::Opaque => 1.0,
+            AlphaMask::Constant(alpha) => alpha.clamp(0.0, 1.0),
+            AlphaMask::Checker { scale, alpha } => {
+                let cell = (point.x * scale).floor() + (point.y * scale).floor() + (point.z * scale).floor();
+                if (cell as i64).rem_euclid(2) == 0 {
+                    1.0
+                } else {
+                    alpha.clamp(0.0, 1.0)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Material {
+    pub color: Vec3,
+    // Fraction de lumière transmise à travers la surface (0 = opaque, 1 = verre clair), teintée par la couleur
+    pub transmission: f64,
+    pub alpha: AlphaMask,
+    // Lumière émise par la surface (nulle pour un objet qui n'éclaire pas)
+    pub emission: Vec3,
+    // Attrape-ombres : la surface ne montre que les ombres qu'elle reçoit, sur ce qui se trouve
+    // derrière elle (fond transparent ou photo à incruster), et ne porte pas d'ombre elle-même
+    pub shadow_catcher: bool,
+}
+
+impl Material {
+    pub fn new(color: Vec3) -> Self {
+        Material {
+            color,
+            transmission: 0.0,
+            alpha: AlphaMask::Opaque,
+            emission: Vec3::new(0.0, 0.0, 0.0),
+            shadow_catcher: false,
+        }
+    }
+
+    pub fn glass(color: Vec3, transmission: f64) -> Self {
+        Material {
+            color,
+            transmission: transmission.clamp(0.0, 1.0),
+            alpha: AlphaMask::Opaque,
+            emission: Vec3::new(0.0, 0.0, 0.0),
+            shadow_catcher: false,
+        }
+    }
+
+    // Surface lumineuse : `emission` est la couleur émise, multipliée par `strength`
+    pub fn emissive(emission: Vec3, strength: f64) -> Self {
+        Material::new(Vec3::new(0.0, 0.0, 0.0)).with_emission(emission * strength)
+    }
+
+    pub fn shadow_catcher() -> Self {
+        Material {
+            shadow_catcher: true,
+            ..Material::new(Vec3::new(0.0, 0.0, 0.0))
+        }
+    }
+
+    pub fn with_emission(mut self, emission: Vec3) -> Self {
+        self.emission = emission;
+        self
+    }
+
+    pub fn is_emissive(&self) -> bool {
+        self.emission.length_squared() > 0.0
+    }
+
+    pub fn with_alpha(mut self, alpha: AlphaMask) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    // Lumière qui traverse une couche de ce matériau : les trous du masque laissent tout passer,
+    // la partie pleine ne laisse passer que la part transmise, filtrée par la couleur
+    pub fn shadow_transmittance(&self, point: &Vec3) -> Vec3 {
+        if self.shadow_catcher {
+            return Vec3::new(1.0, 1.0, 1.0);
+        }
+        let coverage = self.alpha.coverage(point);
+        let hole = 1.0 - coverage;
+        self.color * (coverage * self.transmission) + Vec3::new(hole, hole, hole)
+    }
+}
@@ -0,0 +1,235 @@
+use super::vec3::*;
+use std::ops::Mul;
+
+// Matrice 3×3 rangée par lignes : rotations, changements d'échelle et de base
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3 {
+    pub rows: [[f64; 3]; 3],
+}
+
+impl Mat3 {
+    pub fn new(rows: [[f64; 3]; 3]) -> Self {
+        Mat3 { rows }
+    }
+
+    pub fn identity() -> Self {
+        Mat3::scale(&Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    // Matrice dont les colonnes sont les vecteurs donnés (passage d'une base locale au monde)
+    pub fn from_cols(x: &Vec3, y: &Vec3, z: &Vec3) -> Self {
+        Mat3::new([[x.x, y.x, z.x], [x.y, y.y, z.y], [x.z, y.z, z.z]])
+    }
+
+    pub fn scale(factors: &Vec3) -> Self {
+        Mat3::new([
+            [factors.x, 0.0, 0.0],
+            [0.0, factors.y, 0.0],
+            [0.0, 0.0, factors.z],
+        ])
+    }
+
+    // Rotation d'`angle` radians autour d'un axe unitaire (formule de Rodrigues)
+    pub fn rotation(axis: &Vec3, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+        let c = 1.0 - cos;
+        Mat3::new([
+            [cos + x * x * c, x * y * c - z * sin, x * z * c + y * sin],
+            [y * x * c + z * sin, cos + y * y * c, y * z * c - x * sin],
+            [z * x * c - y * sin, z * y * c + x * sin, cos + z * z * c],
+        ])
+    }
+
+    pub fn rotation_x(angle: f64) -> Self {
+        Mat3::rotation(&Vec3::new(1.0, 0.0, 0.0), angle)
+    }
+
+    // Même convention que Vec3::rotate_y
+    pub fn rotation_y(angle: f64) -> Self {
+        Mat3::rotation(&Vec3::new(0.0, 1.0, 0.0), angle)
+    }
+
+    pub fn rotation_z(angle: f64) -> Self {
+        Mat3::rotation(&Vec3::new(0.0, 0.0, 1.0), angle)
+    }
+
+    pub fn row(&self, i: usize) -> Vec3 {
+        Vec3::new(self.rows[i][0], self.rows[i][1], self.rows[i][2])
+    }
+
+    pub fn col(&self, j: usize) -> Vec3 {
+        Vec3::new(self.rows[0][j], self.rows[1][j], self.rows[2][j])
+    }
+
+    pub fn transpose(&self) -> Mat3 {
+        Mat3::from_cols(&self.row(0), &self.row(1), &self.row(2))
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.row(0).dot(&self.row(1).cross(&self.row(2)))
+    }
+
+    // None pour une matrice singulière
+    pub fn inverse(&self) -> Option<Mat3> {
+        let determinant = self.determinant();
+        if determinant.abs() < 1e-12 {
+            return None;
+        }
+        // Les lignes de l'inverse sont les produits vectoriels des colonnes
+        let (a, b, c) = (self.col(0), self.col(1), self.col(2));
+        let rows = [b.cross(&c), c.cross(&a), a.cross(&b)].map(|r| r / determinant);
+        Some(Mat3::new(rows.map(|r| [r.x, r.y, r.z])))
+    }
+
+    pub fn approx_eq(&self, other: &Mat3, epsilon: f64) -> bool {
+        (0..3).all(|i| self.row(i).approx_eq(&other.row(i), epsilon))
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        Vec3::new(
+            self.row(0).dot(&v),
+            self.row(1).dot(&v),
+            self.row(2).dot(&v),
+        )
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, other: Mat3) -> Mat3 {
+        Mat3::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.row(i).dot(&other.col(j)))
+        }))
+    }
+}
+
+// Transformation affine en coordonnées homogènes (rangée par lignes, translation en dernière colonne)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub rows: [[f64; 4]; 4],
+}
+
+impl Mat4 {
+    pub fn new(rows: [[f64; 4]; 4]) -> Self {
+        Mat4 { rows }
+    }
+
+    pub fn identity() -> Self {
+        Mat4::from_mat3(&Mat3::identity())
+    }
+
+    // Partie linéaire sans translation
+    pub fn from_mat3(m: &Mat3) -> Self {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in m.rows.iter().enumerate() {
+            rows[i][..3].copy_from_slice(row);
+        }
+        rows[3][3] = 1.0;
+        Mat4::new(rows)
+    }
+
+    pub fn translation(offset: &Vec3) -> Self {
+        let mut m = Mat4::identity();
+        m.rows[0][3] = offset.x;
+        m.rows[1][3] = offset.y;
+        m.rows[2][3] = offset.z;
+        m
+    }
+
+    pub fn scale(factors: &Vec3) -> Self {
+        Mat4::from_mat3(&Mat3::scale(factors))
+    }
+
+    pub fn rotation(axis: &Vec3, angle: f64) -> Self {
+        Mat4::from_mat3(&Mat3::rotation(axis, angle))
+    }
+
+    // Partie linéaire (rotation et échelle) de la transformation
+    pub fn linear(&self) -> Mat3 {
+        Mat3::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.rows[i][j])
+        }))
+    }
+
+    // Point : la translation s'applique, puis la division perspective
+    pub fn transform_point(&self, p: &Vec3) -> Vec3 {
+        let [x, y, z, w] = self.apply([p.x, p.y, p.z, 1.0]);
+        Vec3::new(x, y, z) / w
+    }
+
+    // Direction : la translation ne s'applique pas
+    pub fn transform_vector(&self, v: &Vec3) -> Vec3 {
+        let [x, y, z, _] = self.apply([v.x, v.y, v.z, 0.0]);
+        Vec3::new(x, y, z)
+    }
+
+    // Normale : transposée de l'inverse de la partie linéaire, pour rester perpendiculaire à la surface
+    pub fn transform_normal(&self, n: &Vec3) -> Option<Vec3> {
+        let inverse = self.linear().inverse()?;
+        Some((inverse.transpose() * *n).normalize())
+    }
+
+    fn apply(&self, v: [f64; 4]) -> [f64; 4] {
+        self.rows
+            .map(|row| row.iter().zip(v.iter()).map(|(a, b)| a * b).sum())
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        Mat4::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.rows[j][i])
+        }))
+    }
+
+    // Élimination de Gauss-Jordan avec pivot partiel ; None pour une matrice singulière
+    pub fn inverse(&self) -> Option<Mat4> {
+        let mut a = self.rows;
+        let mut inverse = Mat4::identity().rows;
+        for col in 0..4 {
+            let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+            if a[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(col, pivot);
+            inverse.swap(col, pivot);
+            let factor = 1.0 / a[col][col];
+            for j in 0..4 {
+                a[col][j] *= factor;
+                inverse[col][j] *= factor;
+            }
+            for row in 0..4 {
+                if row != col {
+                    let f = a[row][col];
+                    for j in 0..4 {
+                        a[row][j] -= f * a[col][j];
+                        inverse[row][j] -= f * inverse[col][j];
+                    }
+                }
+            }
+        }
+        Some(Mat4::new(inverse))
+    }
+
+    pub fn approx_eq(&self, other: &Mat4, epsilon: f64) -> bool {
+        self.rows
+            .iter()
+            .flatten()
+            .zip(other.rows.iter().flatten())
+            .all(|(a, b)| approx_eq(*a, *b, epsilon))
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        Mat4::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..4).map(|k| self.rows[i][k] * other.rows[k][j]).sum())
+        }))
+    }
+}
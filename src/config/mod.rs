@@ -1,6 +1,20 @@
 pub mod hittable;
 pub mod ray;
 pub mod vec3;
+pub mod matrix;
+pub mod quaternion;
+pub mod onb;
+pub mod validation;
 pub mod world;
 pub mod light;
-pub mod camera;
\ No newline at end of file
+pub mod camera;
+pub mod ambient;
+pub mod material;
+pub mod background;
+pub mod environment;
+pub mod sky;
+pub mod emitter;
+pub mod animation;
+pub mod scene;
+pub mod aabb;
+pub mod bvh;
\ No newline at end of file
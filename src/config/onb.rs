@@ -0,0 +1,35 @@
+use super::vec3::*;
+
+// Base orthonormée directe construite autour d'une direction `w` (normale, axe...) ; u et v
+// complètent le repère dans le plan perpendiculaire
+#[derive(Debug, Clone, Copy)]
+pub struct Onb {
+    pub u: Vec3,
+    pub v: Vec3,
+    pub w: Vec3,
+}
+
+impl Onb {
+    // Construction sans branchement de Duff et al. (2017), continue partout sauf au pôle -z.
+    // `w` doit être unitaire
+    pub fn from_w(w: &Vec3) -> Self {
+        let sign = 1.0_f64.copysign(w.z);
+        let a = -1.0 / (sign + w.z);
+        let b = w.x * w.y * a;
+        Onb {
+            u: Vec3::new(1.0 + sign * w.x * w.x * a, sign * b, -sign * w.x),
+            v: Vec3::new(b, sign + w.y * w.y * a, -w.y),
+            w: *w,
+        }
+    }
+
+    // Coordonnées locales (u, v, w) -> monde
+    pub fn to_world(&self, local: &Vec3) -> Vec3 {
+        self.u * local.x + self.v * local.y + self.w * local.z
+    }
+
+    // Monde -> coordonnées locales
+    pub fn to_local(&self, v: &Vec3) -> Vec3 {
+        Vec3::new(v.dot(&self.u), v.dot(&self.v), v.dot(&self.w))
+    }
+}
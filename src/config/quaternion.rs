@@ -0,0 +1,115 @@
+use super::matrix::*;
+use super::vec3::*;
+use std::ops::Mul;
+
+// Quaternion unitaire représentant une rotation : se compose sans blocage de cardan et
+// s'interpole à vitesse angulaire constante (slerp)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quat {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Quat { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Quat::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    // Rotation d'`angle` radians autour d'un axe (normalisé ici)
+    pub fn from_axis_angle(axis: &Vec3, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        Quat::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+    }
+
+    fn vector(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(&self, other: &Quat) -> f64 {
+        self.w * other.w + self.vector().dot(&other.vector())
+    }
+
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    // Comme Vec3::normalize, un quaternion nul reste nul au lieu de donner des NaN
+    pub fn normalize(&self) -> Quat {
+        let len = self.length();
+        if len == 0.0 {
+            return *self;
+        }
+        Quat::new(self.w / len, self.x / len, self.y / len, self.z / len)
+    }
+
+    // Rotation inverse pour un quaternion unitaire
+    pub fn conjugate(&self) -> Quat {
+        Quat::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn rotate(&self, v: &Vec3) -> Vec3 {
+        // v + 2 q × (q × v + w v), sans passer par deux produits de quaternions
+        let q = self.vector();
+        let t = q.cross(v) * 2.0;
+        *v + t * self.w + q.cross(&t)
+    }
+
+    pub fn to_mat3(&self) -> Mat3 {
+        Mat3::from_cols(
+            &self.rotate(&Vec3::new(1.0, 0.0, 0.0)),
+            &self.rotate(&Vec3::new(0.0, 1.0, 0.0)),
+            &self.rotate(&Vec3::new(0.0, 0.0, 1.0)),
+        )
+    }
+
+    // Interpolation sphérique (t = 0 : self, t = 1 : other) par le plus court chemin
+    pub fn slerp(&self, other: &Quat, t: f64) -> Quat {
+        let mut cos = self.dot(other);
+        let mut other = *other;
+        if cos < 0.0 {
+            // q et -q représentent la même rotation : on prend l'arc le plus court
+            cos = -cos;
+            other = Quat::new(-other.w, -other.x, -other.y, -other.z);
+        }
+        let (a, b) = if cos > 0.9995 {
+            // Quaternions presque confondus : interpolation linéaire puis normalisation
+            (1.0 - t, t)
+        } else {
+            let theta = cos.acos();
+            let sin = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        Quat::new(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+        )
+        .normalize()
+    }
+
+    // Même rotation à epsilon près, composante par composante (q et -q sont confondus)
+    pub fn approx_eq(&self, other: &Quat, epsilon: f64) -> bool {
+        let sign = if self.dot(other) < 0.0 { -1.0 } else { 1.0 };
+        approx_eq(self.w, sign * other.w, epsilon)
+            && self.vector().approx_eq(&(other.vector() * sign), epsilon)
+    }
+}
+
+// Composition : (a * b).rotate(v) == a.rotate(&b.rotate(v))
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, other: Quat) -> Quat {
+        let (a, b) = (self.vector(), other.vector());
+        let v = b * self.w + a * other.w + a.cross(&b);
+        Quat::new(self.w * other.w - a.dot(&b), v.x, v.y, v.z)
+    }
+}
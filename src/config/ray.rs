@@ -2,6 +2,7 @@ use super::vec3::*;
 pub struct Ray {
     pub origin: Vec3,
     pub direction: Vec3,
+    pub time: f64, // Instant auquel le rayon traverse la scène (flou de mouvement)
 }
 
 impl Ray {
@@ -9,10 +10,16 @@ impl Ray {
         Ray {
             origin,
             direction: direction.normalize(),
+            time: 0.0,
         }
     }
 
+    pub fn at_time(mut self, time: f64) -> Self {
+        self.time = time;
+        self
+    }
+
     pub fn point_at_parameter(&self, t: f64) -> Vec3 {
-        self.origin.add(&self.direction.mul(t))
+        self.origin + self.direction * t
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,156 @@
+use super::ambient::*;
+use super::animation::*;
+use super::background::*;
+use super::camera::*;
+use super::emitter::*;
+use super::light::*;
+use super::validation::*;
+use super::world::World;
+
+// Tout ce qu'il faut pour éclairer un rayon : objets, lumières, ambiant, fond et réglages d'ombrage
+pub struct Scene {
+    pub world: World,
+    pub lights: Vec<Light>,
+    pub emitters: EmitterList,
+    pub ambient: Ambient,
+    pub background: Box<dyn Background>,
+    pub shading: Shading,
+    pub light_animations: Vec<LightAnimation>,
+    pub timeline: Timeline,
+}
+
+impl Scene {
+    pub fn new(world: World, lights: Vec<Light>, background: Box<dyn Background>) -> Self {
+        // Les objets émissifs (matériau avec émission) éclairent aussi la scène
+        let emitters = EmitterList::new(&world);
+        let mut scene = Scene {
+            world,
+            lights,
+            emitters,
+            ambient: Ambient::default(),
+            background,
+            shading: Shading::default(),
+            light_animations: Vec::new(),
+            timeline: Timeline::default(),
+        };
+        scene.build_bvh();
+        scene
+    }
+
+    pub fn with_ambient(mut self, ambient: Ambient) -> Self {
+        self.ambient = ambient;
+        self
+    }
+
+    pub fn with_shading(mut self, shading: Shading) -> Self {
+        self.shading = shading;
+        self
+    }
+
+    pub fn with_timeline(mut self, timeline: Timeline) -> Self {
+        self.set_timeline(timeline);
+        self
+    }
+
+    pub fn set_timeline(&mut self, timeline: Timeline) {
+        self.timeline = timeline;
+        self.build_bvh();
+    }
+
+    // Hiérarchie de boîtes valable de l'ouverture du premier obturateur à la fermeture du dernier
+    pub fn build_bvh(&mut self) {
+        let first = self.timeline.shutter(self.timeline.start_frame);
+        let last = self.timeline.shutter(self.timeline.end_frame);
+        self.world.build_bvh(first.open, last.close);
+    }
+
+    pub fn animate_light(mut self, animation: LightAnimation) -> Self {
+        self.light_animations.push(animation);
+        self
+    }
+
+    // Place objets et lumières à l'instant donné (secondes)
+    pub fn set_time(&mut self, time: f64) {
+        self.world.set_time(time);
+        for animation in &self.light_animations {
+            // Une animation qui vise une lumière absente est ignorée
+            let Some(light) = self.lights.get_mut(animation.light) else {
+                continue;
+            };
+            if let Some(position) = animation.position.as_ref().and_then(|p| p.evaluate(time)) {
+                light.position = position;
+            }
+            if let Some(intensity) = animation.intensity.as_ref().and_then(|i| i.evaluate(time)) {
+                light.intensity = intensity;
+            }
+            if let Some(color) = animation.color.as_ref().and_then(|c| c.evaluate(time)) {
+                light.color = color;
+            }
+        }
+        // L'émission des objets peut avoir changé
+        self.emitters = EmitterList::new(&self.world);
+    }
+
+    // Relève ce qui donnerait une image fausse sans erreur visible : objets dégénérés (pixels NaN),
+    // objets entièrement derrière la caméra, lumières invalides ou enfermées dans un objet
+    pub fn validate(&self, camera: &CameraSettings) -> Vec<SceneIssue> {
+        let mut issues = Vec::new();
+        let first = self.timeline.shutter(self.timeline.start_frame);
+        let last = self.timeline.shutter(self.timeline.end_frame);
+
+        // Seules les projections qui ne voient pas derrière elles sont concernées
+        let forward = match camera.projection {
+            Projection::Perspective { .. } | Projection::Orthographic { .. } => {
+                Some(camera.lookat - camera.lookfrom)
+            }
+            Projection::Fisheye { fov, .. } if fov <= 180.0 => Some(camera.lookat - camera.lookfrom),
+            _ => None,
+        };
+
+        for (object, hittable) in self.world.objects.iter().enumerate() {
+            if let Err(error) = hittable.validate() {
+                issues.push(SceneIssue::DegenerateObject { object, error });
+                continue;
+            }
+            let behind = match (forward, hittable.bounding_box(first.open, last.close)) {
+                (Some(forward), Some(bbox)) => bbox
+                    .corners()
+                    .iter()
+                    .all(|corner| (*corner - camera.lookfrom).dot(&forward) <= 0.0),
+                _ => false,
+            };
+            if behind {
+                issues.push(SceneIssue::BehindCamera { object });
+            }
+        }
+
+        for (light, source) in self.lights.iter().enumerate() {
+            let checked = match source.direction {
+                Some(direction) => check_axis(&direction, "direction").map(|_| ()),
+                None => check_finite(&source.position, "position"),
+            }
+            .and_then(|_| check_finite(&source.color, "couleur"))
+            .and_then(|_| {
+                if source.intensity.is_finite() {
+                    Ok(())
+                } else {
+                    Err(GeometryError::NotFinite { field: "intensité" })
+                }
+            });
+            if let Err(error) = checked {
+                issues.push(SceneIssue::InvalidLight { light, error });
+                continue;
+            }
+            // Une lumière directionnelle n'a pas de position
+            if source.direction.is_some() {
+                continue;
+            }
+            for (object, hittable) in self.world.objects.iter().enumerate() {
+                if hittable.validate().is_ok() && hittable.contains(&source.position) {
+                    issues.push(SceneIssue::LightInside { light, object });
+                }
+            }
+        }
+        issues
+    }
+}
@@ -0,0 +1,185 @@
+use super::background::*;
+use super::light::*;
+use super::vec3::*;
+use std::f64::consts::PI;
+
+// Ciel procédural de Preetham (1999) : luminance et chromaticité selon la position du soleil et la turbidité
+pub struct Sky {
+    pub sun_direction: Vec3,
+    pub turbidity: f64,
+    pub intensity: f64, // Luminance du zénith
+    pub ground: Vec3,   // Couleur sous l'horizon
+    pub sun_size: f64,  // Rayon angulaire du disque solaire (radians)
+    pub sun_intensity: f64,
+    // Coefficients A..E de la fonction de Perez pour Y, x et y
+    perez: [[f64; 5]; 3],
+    // Valeurs au zénith (Y relatif, x, y) divisées par la fonction de Perez au zénith
+    zenith: [f64; 3],
+}
+
+impl Sky {
+    // Élévation au-dessus de l'horizon et azimut (depuis -Z vers +X), en degrés
+    pub fn new(elevation: f64, azimuth: f64, turbidity: f64) -> Self {
+        let elevation = elevation.to_radians();
+        let azimuth = azimuth.to_radians();
+        let sun_direction = Vec3::new(
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+            -elevation.cos() * azimuth.cos(),
+        );
+        let t = turbidity.max(1.0);
+        let perez = [
+            [
+                0.1787 * t - 1.4630,
+                -0.3554 * t + 0.4275,
+                -0.0227 * t + 5.3251,
+                0.1206 * t - 2.5771,
+                -0.0670 * t + 0.3703,
+            ],
+            [
+                -0.0193 * t - 0.2592,
+                -0.0665 * t + 0.0008,
+                -0.0004 * t + 0.2125,
+                -0.0641 * t - 0.8989,
+                -0.0033 * t + 0.0452,
+            ],
+            [
+                -0.0167 * t - 0.2608,
+                -0.0950 * t + 0.0092,
+                -0.0079 * t + 0.2102,
+                -0.0441 * t - 1.6537,
+                -0.0109 * t + 0.0529,
+            ],
+        ];
+
+        // Angle zénithal du soleil, limité pour garder un ciel défini au crépuscule
+        let theta_s = (PI / 2.0 - elevation).clamp(0.0, PI / 2.0 - 0.01);
+        let (t1, t2, t3) = (theta_s, theta_s * theta_s, theta_s * theta_s * theta_s);
+        let x_zenith = t * t * (0.00166 * t3 - 0.00375 * t2 + 0.00209 * t1)
+            + t * (-0.02903 * t3 + 0.06377 * t2 - 0.03202 * t1 + 0.00394)
+            + (0.11693 * t3 - 0.21196 * t2 + 0.06052 * t1 + 0.25886);
+        let y_zenith = t * t * (0.00275 * t3 - 0.00610 * t2 + 0.00317 * t1)
+            + t * (-0.04214 * t3 + 0.08970 * t2 - 0.04153 * t1 + 0.00516)
+            + (0.15346 * t3 - 0.26756 * t2 + 0.06670 * t1 + 0.26688);
+
+        let zenith = [
+            1.0 / perez_function(&perez[0], 1.0, theta_s),
+            x_zenith / perez_function(&perez[1], 1.0, theta_s),
+            y_zenith / perez_function(&perez[2], 1.0, theta_s),
+        ];
+
+        Sky {
+            sun_direction,
+            turbidity: t,
+            intensity: 1.0,
+            ground: Vec3::new(0.3, 0.3, 0.3),
+            sun_size: 0.53_f64.to_radians() / 2.0,
+            sun_intensity: 20.0,
+            perez,
+            zenith,
+        }
+    }
+
+    pub fn with_intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    pub fn with_ground(mut self, ground: Vec3) -> Self {
+        self.ground = ground;
+        self
+    }
+
+    // Couleur du soleil après traversée de l'atmosphère (diffusion Rayleigh et aérosols)
+    pub fn sun_color(&self) -> Vec3 {
+        let elevation = self.sun_direction.y.clamp(-1.0, 1.0).asin().to_degrees();
+        if elevation <= 0.0 {
+            return Vec3::new(0.0, 0.0, 0.0);
+        }
+        // Masse d'air relative (Kasten et Young)
+        let air_mass =
+            1.0 / ((elevation.to_radians()).sin() + 0.50572 * (elevation + 6.07995).powf(-1.6364));
+        // Épaisseurs optiques pour des longueurs d'onde représentatives du rouge, vert et bleu
+        let beta = 0.04608 * self.turbidity - 0.04586;
+        let transmittance = |lambda: f64| {
+            let rayleigh = 0.008569
+                * lambda.powi(-4)
+                * (1.0 + 0.0113 * lambda.powi(-2) + 0.00013 * lambda.powi(-4));
+            let aerosol = beta * lambda.powf(-1.3);
+            (-air_mass * (rayleigh + aerosol)).exp()
+        };
+        Vec3::new(
+            transmittance(0.68),
+            transmittance(0.55),
+            transmittance(0.44),
+        )
+    }
+
+    // Lumière directionnelle correspondant au soleil du ciel
+    pub fn sun_light(&self, intensity: f64) -> Light {
+        Light::directional(self.sun_direction, intensity, self.sun_color())
+    }
+
+    fn sky_radiance(&self, direction: &Vec3) -> Vec3 {
+        // Sous l'horizon on évalue à l'horizon, assombri par le sol
+        let cos_theta = direction.y.max(0.001);
+        let gamma = direction.dot(&self.sun_direction).clamp(-1.0, 1.0).acos();
+
+        let luminance =
+            self.zenith[0] * perez_function(&self.perez[0], cos_theta, gamma) * self.intensity;
+        let x = self.zenith[1] * perez_function(&self.perez[1], cos_theta, gamma);
+        let y = self.zenith[2] * perez_function(&self.perez[2], cos_theta, gamma);
+
+        let color = xyy_to_rgb(x, y, luminance);
+        if direction.y < 0.0 {
+            color * self.ground
+        } else {
+            color
+        }
+    }
+}
+
+impl Background for Sky {
+    fn radiance(&self, direction: &Vec3) -> Vec3 {
+        let direction = direction.normalize();
+        let sky = self.sky_radiance(&direction);
+        if direction.dot(&self.sun_direction) > self.sun_size.cos() {
+            return sky + self.sun_color() * self.sun_intensity;
+        }
+        sky
+    }
+
+    // Échantillonnage uniforme de la sphère ; le soleil est éclairé par sa lumière directionnelle
+    fn sample(&self, u: f64, v: f64) -> Option<BackgroundSample> {
+        let z = 1.0 - 2.0 * u;
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * PI * v;
+        let direction = Vec3::new(r * phi.cos(), z, r * phi.sin());
+        Some(BackgroundSample {
+            direction,
+            radiance: self.sky_radiance(&direction),
+            pdf: 1.0 / (4.0 * PI),
+        })
+    }
+}
+
+// Fonction de distribution de Perez : (1 + A e^(B / cos θ)) (1 + C e^(D γ) + E cos² γ)
+fn perez_function(coefficients: &[f64; 5], cos_theta: f64, gamma: f64) -> f64 {
+    let [a, b, c, d, e] = *coefficients;
+    let cos_theta = cos_theta.max(0.001);
+    (1.0 + a * (b / cos_theta).exp()) * (1.0 + c * (d * gamma).exp() + e * gamma.cos().powi(2))
+}
+
+// Chromaticité xyY -> RGB linéaire (sRGB, blanc D65)
+fn xyy_to_rgb(x: f64, y: f64, luminance: f64) -> Vec3 {
+    if y <= 0.0 {
+        return Vec3::new(0.0, 0.0, 0.0);
+    }
+    let big_x = x / y * luminance;
+    let big_z = (1.0 - x - y) / y * luminance;
+    Vec3::new(
+        (3.2406 * big_x - 1.5372 * luminance - 0.4986 * big_z).max(0.0),
+        (-0.9689 * big_x + 1.8758 * luminance + 0.0415 * big_z).max(0.0),
+        (0.0557 * big_x - 0.2040 * luminance + 1.0570 * big_z).max(0.0),
+    )
+}
@@ -0,0 +1,105 @@
+use super::vec3::*;
+use std::fmt;
+use std::io;
+
+// Paramètre géométrique refusé par un constructeur try_new ou par Hittable::validate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeometryError {
+    // Vecteur nul ou presque : pas de direction
+    ZeroVector,
+    // NaN ou infini
+    NotFinite { field: &'static str },
+    // Axe ou normale nulle, ou NaN après normalisation
+    ZeroAxis { field: &'static str },
+    // Rayon, hauteur... nul, négatif ou NaN
+    NotPositive { field: &'static str, value: f64 },
+    // Cube dont min n'est pas inférieur à max sur un axe
+    EmptyBox,
+    // Sommets alignés ou confondus
+    DegenerateTriangle,
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryError::ZeroVector => write!(f, "vecteur nul, impossible à normaliser"),
+            GeometryError::NotFinite { field } => {
+                write!(f, "{} : valeur non finie (NaN ou infini)", field)
+            }
+            GeometryError::ZeroAxis { field } => write!(f, "{} : vecteur nul ou invalide", field),
+            GeometryError::NotPositive { field, value } => {
+                write!(
+                    f,
+                    "{} : doit être strictement positif (reçu {})",
+                    field, value
+                )
+            }
+            GeometryError::EmptyBox => write!(
+                f,
+                "boîte vide : min doit être inférieur à max sur chaque axe"
+            ),
+            GeometryError::DegenerateTriangle => write!(f, "triangle dégénéré (sommets alignés)"),
+        }
+    }
+}
+
+impl std::error::Error for GeometryError {}
+
+// Permet `?` dans les fonctions qui renvoient io::Result, comme main
+impl From<GeometryError> for io::Error {
+    fn from(error: GeometryError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, error)
+    }
+}
+
+pub fn check_finite(v: &Vec3, field: &'static str) -> Result<(), GeometryError> {
+    if v.is_finite() {
+        Ok(())
+    } else {
+        Err(GeometryError::NotFinite { field })
+    }
+}
+
+// Le test est écrit pour que NaN soit refusé
+pub fn check_positive(value: f64, field: &'static str) -> Result<(), GeometryError> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(GeometryError::NotPositive { field, value })
+    }
+}
+
+// Direction unitaire à partir d'un axe quelconque ; un axe déjà normalisé depuis un vecteur nul
+// contient des NaN et est refusé de la même façon
+pub fn check_axis(axis: &Vec3, field: &'static str) -> Result<Vec3, GeometryError> {
+    axis.try_normalize()
+        .map_err(|_| GeometryError::ZeroAxis { field })
+}
+
+// Problème relevé par Scene::validate ; les indices sont ceux de world.objects et de lights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SceneIssue {
+    DegenerateObject { object: usize, error: GeometryError },
+    InvalidLight { light: usize, error: GeometryError },
+    BehindCamera { object: usize },
+    LightInside { light: usize, object: usize },
+}
+
+impl fmt::Display for SceneIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SceneIssue::DegenerateObject { object, error } => {
+                write!(f, "objet {} dégénéré : {}", object, error)
+            }
+            SceneIssue::InvalidLight { light, error } => {
+                write!(f, "lumière {} invalide : {}", light, error)
+            }
+            SceneIssue::BehindCamera { object } => {
+                write!(f, "objet {} entièrement derrière la caméra", object)
+            }
+            SceneIssue::LightInside { light, object } => {
+                write!(f, "lumière {} à l'intérieur de l'objet {}", light, object)
+            }
+        }
+    }
+}
@@ -1,4 +1,8 @@
-#[derive(Debug, Clone, Copy)]
+use super::validation::*;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Deref, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vec3 {
     pub x: f64,
     pub y: f64,
@@ -30,8 +34,12 @@ impl Vec3 {
         self.x * self.x + self.y * self.y + self.z * self.z
     }
 
+    // Un vecteur nul reste nul au lieu de donner des NaN ; pour le détecter, utiliser try_normalize
     pub fn normalize(&self) -> Vec3 {
         let len = self.length();
+        if len == 0.0 {
+            return *self;
+        }
         Vec3 {
             x: self.x / len,
             y: self.y / len,
@@ -39,6 +47,19 @@ impl Vec3 {
         }
     }
 
+    // Comme normalize, mais refuse les vecteurs nuls ou non finis au lieu de produire des NaN
+    pub fn try_normalize(&self) -> Result<Vec3, GeometryError> {
+        let len = self.length();
+        if len < 1e-12 || !len.is_finite() {
+            return Err(GeometryError::ZeroVector);
+        }
+        Ok(*self / len)
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+
     pub fn add(&self, other: &Vec3) -> Vec3 {
         Vec3 {
             x: self.x + other.x,
@@ -62,4 +83,319 @@ impl Vec3 {
             z: self.z * scalar,
         }
     }
+
+    // Produit composante par composante (utile pour filtrer une couleur par une autre)
+    pub fn mul_vec(&self, other: &Vec3) -> Vec3 {
+        Vec3 {
+            x: self.x * other.x,
+            y: self.y * other.y,
+            z: self.z * other.z,
+        }
+    }
+
+    // Luminance relative (Rec. 709) lorsque le vecteur représente une couleur
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.x + 0.7152 * self.y + 0.0722 * self.z
+    }
+
+    // Rotation autour de l'axe Y (radians)
+    pub fn rotate_y(&self, angle: f64) -> Vec3 {
+        let (sin_theta, cos_theta) = angle.sin_cos();
+        Vec3 {
+            x: self.x * cos_theta + self.z * sin_theta,
+            y: self.y,
+            z: -self.x * sin_theta + self.z * cos_theta,
+        }
+    }
+
+    // Symétrique de la direction par rapport au plan de normale `n` (unitaire)
+    pub fn reflect(&self, n: &Vec3) -> Vec3 {
+        *self - *n * (2.0 * self.dot(n))
+    }
+
+    // Direction réfractée (loi de Snell) pour une direction et une normale unitaires opposées ;
+    // `eta` est le rapport des indices (milieu quitté / milieu traversé). None en réflexion totale
+    pub fn refract(&self, n: &Vec3, eta: f64) -> Option<Vec3> {
+        let cos_i = (-self.dot(n)).min(1.0);
+        let sin2_t = eta * eta * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            return None;
+        }
+        Some(*self * eta + *n * (eta * cos_i - (1.0 - sin2_t).sqrt()))
+    }
+
+    // Égalité composante par composante à `epsilon` près
+    pub fn approx_eq(&self, other: &Vec3, epsilon: f64) -> bool {
+        approx_eq(self.x, other.x, epsilon)
+            && approx_eq(self.y, other.y, epsilon)
+            && approx_eq(self.z, other.z, epsilon)
+    }
+}
+
+// Tolérance par défaut des comparaisons approchées
+pub const EPSILON: f64 = 1e-9;
+
+// Égalité à `epsilon` près, en absolu pour les petites valeurs et en relatif pour les grandes
+pub fn approx_eq(a: f64, b: f64, epsilon: f64) -> bool {
+    a == b || (a - b).abs() <= epsilon * a.abs().max(b.abs()).max(1.0)
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scalar: f64) -> Vec3 {
+        Vec3::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+impl Mul<Vec3> for f64 {
+    type Output = Vec3;
+
+    fn mul(self, vector: Vec3) -> Vec3 {
+        vector * self
+    }
+}
+
+// Produit composante par composante, comme mul_vec
+impl Mul for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+}
+
+impl Div<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn div(self, scalar: f64) -> Vec3 {
+        Vec3::new(self.x / scalar, self.y / scalar, self.z / scalar)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Vec3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Vec3) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f64> for Vec3 {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+impl DivAssign<f64> for Vec3 {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+impl Sum for Vec3 {
+    fn sum<I: Iterator<Item = Vec3>>(iter: I) -> Vec3 {
+        iter.fold(Vec3::new(0.0, 0.0, 0.0), |sum, v| sum + v)
+    }
+}
+
+// Position dans l'espace : la différence de deux points est un vecteur, un point plus un vecteur
+// est un point, mais deux points ne s'additionnent pas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point3(pub Vec3);
+
+impl Point3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Point3(Vec3::new(x, y, z))
+    }
+
+    pub fn origin() -> Self {
+        Point3::new(0.0, 0.0, 0.0)
+    }
+
+    pub fn distance(&self, other: &Point3) -> f64 {
+        (*self - *other).length()
+    }
+
+    // Interpolation linéaire (t = 0 : self, t = 1 : other)
+    pub fn lerp(&self, other: &Point3, t: f64) -> Point3 {
+        *self + (*other - *self) * t
+    }
+}
+
+impl From<Vec3> for Point3 {
+    fn from(v: Vec3) -> Self {
+        Point3(v)
+    }
+}
+
+impl From<Point3> for Vec3 {
+    fn from(p: Point3) -> Self {
+        p.0
+    }
+}
+
+// Les méthodes de Vec3 (x, y, z, dot...) restent accessibles directement sur un point
+impl Deref for Point3 {
+    type Target = Vec3;
+
+    fn deref(&self) -> &Vec3 {
+        &self.0
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Point3) -> Vec3 {
+        self.0 - other.0
+    }
+}
+
+impl Add<Vec3> for Point3 {
+    type Output = Point3;
+
+    fn add(self, offset: Vec3) -> Point3 {
+        Point3(self.0 + offset)
+    }
+}
+
+impl Sub<Vec3> for Point3 {
+    type Output = Point3;
+
+    fn sub(self, offset: Vec3) -> Point3 {
+        Point3(self.0 - offset)
+    }
+}
+
+impl AddAssign<Vec3> for Point3 {
+    fn add_assign(&mut self, offset: Vec3) {
+        self.0 += offset;
+    }
+}
+
+// Couleur RGB linéaire : s'additionne, se module par une autre couleur ou un facteur
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub Vec3);
+
+impl Color {
+    pub fn new(r: f64, g: f64, b: f64) -> Self {
+        Color(Vec3::new(r, g, b))
+    }
+
+    pub fn black() -> Self {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    pub fn white() -> Self {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    pub fn is_black(&self) -> bool {
+        self.0.x == 0.0 && self.0.y == 0.0 && self.0.z == 0.0
+    }
+}
+
+impl From<Vec3> for Color {
+    fn from(v: Vec3) -> Self {
+        Color(v)
+    }
+}
+
+impl From<Color> for Vec3 {
+    fn from(c: Color) -> Self {
+        c.0
+    }
+}
+
+impl Deref for Color {
+    type Target = Vec3;
+
+    fn deref(&self) -> &Vec3 {
+        &self.0
+    }
+}
+
+impl Add for Color {
+    type Output = Color;
+
+    fn add(self, other: Color) -> Color {
+        Color(self.0 + other.0)
+    }
+}
+
+impl Mul for Color {
+    type Output = Color;
+
+    fn mul(self, other: Color) -> Color {
+        Color(self.0 * other.0)
+    }
+}
+
+impl Mul<f64> for Color {
+    type Output = Color;
+
+    fn mul(self, factor: f64) -> Color {
+        Color(self.0 * factor)
+    }
+}
+
+impl Div<f64> for Color {
+    type Output = Color;
+
+    fn div(self, factor: f64) -> Color {
+        Color(self.0 / factor)
+    }
+}
+
+impl AddAssign for Color {
+    fn add_assign(&mut self, other: Color) {
+        self.0 += other.0;
+    }
+}
+
+impl MulAssign for Color {
+    fn mul_assign(&mut self, other: Color) {
+        self.0 = self.0 * other.0;
+    }
+}
+
+impl MulAssign<f64> for Color {
+    fn mul_assign(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+impl Sum for Color {
+    fn sum<I: Iterator<Item = Color>>(iter: I) -> Color {
+        Color(iter.map(|c| c.0).sum())
+    }
 }
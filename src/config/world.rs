@@ -1,31 +1,89 @@
-
+use super::bvh::*;
 use super::hittable::*;
 use super::ray::*;
 
 pub struct World {
     pub objects: Vec<Box<dyn Hittable>>,
+    // Accélération optionnelle (voir build_bvh) ; les objets sans boîte restent testés un par un
+    bvh: Option<Bvh>,
+    unbounded: Vec<usize>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl World {
     pub fn new() -> Self {
         World {
             objects: Vec::new(),
+            bvh: None,
+            unbounded: Vec::new(),
         }
     }
 
     pub fn add(&mut self, object: Box<dyn Hittable>) {
         self.objects.push(object);
+        // La hiérarchie ne connaît pas le nouvel objet : elle devra être reconstruite
+        self.bvh = None;
+    }
+
+    // Construit la hiérarchie de boîtes pour des rayons dont le temps est compris dans [time0, time1]
+    pub fn build_bvh(&mut self, time0: f64, time1: f64) {
+        let mut bounded = Vec::new();
+        self.unbounded.clear();
+        for (index, object) in self.objects.iter().enumerate() {
+            match object.bounding_box(time0, time1) {
+                Some(aabb) => bounded.push((index, aabb)),
+                None => self.unbounded.push(index),
+            }
+        }
+        self.bvh = Some(Bvh::new(bounded, time0, time1));
+    }
+
+    pub fn set_time(&mut self, time: f64) {
+        for object in &mut self.objects {
+            object.set_time(time);
+        }
     }
 
     pub fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
-        let mut closest_hit: Option<HitRecord> = None;
+        self.hit_object(ray, t_min, t_max).map(|(_, hit_record)| hit_record)
+    }
+
+    // Intersection la plus proche et indice de l'objet touché dans `objects`
+    pub fn hit_object(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<(usize, HitRecord)> {
+        // Hors de l'intervalle de la hiérarchie, les boîtes ne sont plus garanties
+        let bvh = self
+            .bvh
+            .as_ref()
+            .filter(|bvh| ray.time >= bvh.time0 && ray.time <= bvh.time1);
+        let Some(bvh) = bvh else {
+            return self.hit_objects(0..self.objects.len(), ray, t_min, t_max);
+        };
+
+        let closest_hit = bvh.hit(&self.objects, ray, t_min, t_max);
+        let closest_t = closest_hit.as_ref().map_or(t_max, |(_, hit_record)| hit_record.t);
+        self.hit_objects(self.unbounded.iter().copied(), ray, t_min, closest_t).or(closest_hit)
+    }
+
+    fn hit_objects(
+        &self,
+        indices: impl Iterator<Item = usize>,
+        ray: &Ray,
+        t_min: f64,
+        t_max: f64,
+    ) -> Option<(usize, HitRecord)> {
+        let mut closest_hit: Option<(usize, HitRecord)> = None;
         let mut closest_t = t_max;
 
-        for object in &self.objects {
-            if let Some(hit_record) = object.hit(ray, t_min, closest_t) {
+        for index in indices {
+            if let Some(hit_record) = self.objects[index].hit(ray, t_min, closest_t) {
                 if hit_record.t < closest_t {
                     closest_t = hit_record.t;
-                    closest_hit = Some(hit_record);
+                    closest_hit = Some((index, hit_record));
                 }
             }
         }
@@ -1,6 +1,10 @@
 mod shape;
 mod config;
+mod projection;
+mod render;
 
 
 pub use shape::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use projection::*;
+pub use render::*;
\ No newline at end of file
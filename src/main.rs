@@ -1,70 +1,169 @@
 use indicatif::{ProgressBar, ProgressStyle};
+use rt::ambient::*;
+use rt::animated::*;
+use rt::animation::*;
+use rt::aov::*;
+use rt::background::*;
 use rt::camera::*;
 use rt::cube::*;
 use rt::cylindre::*;
+use rt::denoise::*;
+use rt::filter::*;
+use rt::image::*;
+use rt::fisheye::*;
+use rt::environment::*;
 use rt::light::*;
+use rt::material::*;
 use rt::plane::*;
-use rt::ray::*;
+use rt::progressive::*;
+use rt::renderer::*;
+use rt::sampler::*;
+use rt::scene::*;
+use rt::sky::*;
 use rt::sphere::*;
 use rt::vec3::*;
 use rt::world::*;
 use std::f64::consts::PI;
-use std::fs::File;
-use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-fn ray_color(ray: &Ray, world: &World, lights: &[Light]) -> Vec3 {
-    let background_color = Vec3::new(0.5, 0.7, 1.0);
+// Levé par Ctrl-C pendant un rendu progressif
+static STOP: AtomicBool = AtomicBool::new(false);
 
-    match world.hit(ray, 0.001, f64::INFINITY) {
-        Some(hit_record) => {
-            let mut total_color = Vec3::new(0.0, 0.0, 0.0);
+fn main() -> std::io::Result<()> {
+    let width = 800;
+    let height = 600;
+    let args: Vec<String> = std::env::args().collect();
+    let option = |name: &str| args.iter().position(|a| a == name);
 
-            for light in lights {
-                // Passage du world en paramètre
-                let light_color = calculate_lighting(&hit_record, light, world);
-                total_color = total_color.add(&light_color);
-            }
+    // Échantillons par pixel (--samples N), et maximum d'un rendu progressif s'il est donné
+    let explicit_samples = option("--samples")
+        .and_then(|index| args.get(index + 1))
+        .map(|a| a.parse::<usize>().expect("--samples attend un entier"));
+    let samples = explicit_samples.unwrap_or(10);
 
-            total_color
+    // Graine des nombres aléatoires (--seed N) : deux rendus avec la même graine sont identiques
+    let seed = option("--seed")
+        .and_then(|index| args.get(index + 1))
+        .map(|a| a.parse::<u64>().expect("--seed attend un entier"))
+        .unwrap_or(0);
+    // Échantillonneur (--sampler independent|stratified|halton|sobol|bluenoise)
+    let sampler = match option("--sampler").and_then(|index| args.get(index + 1)) {
+        Some(name) => SamplerKind::from_name(name).unwrap_or_else(|| panic!("échantillonneur inconnu : {}", name)),
+        None => SamplerKind::Sobol,
+    };
+    // Échantillonnage adaptatif (--adaptive seuil) : de 8 à 64 échantillons selon le bruit de chaque pixel
+    let adaptive = option("--adaptive")
+        .and_then(|index| args.get(index + 1))
+        .map(|a| AdaptiveSampling::new(8, 64, a.parse::<f64>().expect("--adaptive attend un seuil")));
+    // Filtre de reconstruction (--filter box|tent|gaussian|mitchell|lanczos [rayon])
+    let filter = match option("--filter") {
+        Some(index) => {
+            let name = args.get(index + 1).expect("--filter attend un nom de filtre");
+            let filter = Filter::from_name(name).unwrap_or_else(|| panic!("filtre inconnu : {}", name));
+            match args.get(index + 2).and_then(|a| a.parse::<f64>().ok()) {
+                Some(radius) => filter.with_radius(radius),
+                None => filter,
+            }
         }
-        None => {
-            let unit_direction = ray.direction;
-            let t = 0.5 * (unit_direction.y + 1.0);
-            background_color
-                .mul(1.0 - t)
-                .add(&Vec3::new(1.0, 1.0, 1.0).mul(t))
+        None => Filter::default(),
+    };
+    // AOV (--aov rendu.exr pour un EXR multicouche, --aov préfixe pour préfixe_depth.png, préfixe_normal.png...)
+    let aov_output = option("--aov").map(|index| args.get(index + 1).expect("--aov attend un fichier ou un préfixe"));
+    // Débruitage de l'image finale (--denoise [itérations]), guidé par les AOV
+    let denoiser = option("--denoise").map(|index| {
+        match args.get(index + 1).and_then(|a| a.parse::<usize>().ok()) {
+            Some(iterations) => Denoiser::default().with_iterations(iterations),
+            None => Denoiser::default(),
         }
+    });
+    // Image produite (--output fichier.png|.exr|.ppm) ; --transparent remplace le fond par un canal alpha
+    let output_path = option("--output").map_or("world_scene.ppm", |index| {
+        args.get(index + 1).expect("--output attend un fichier")
+    });
+    let transparent = option("--transparent").is_some();
+    // Le PPM n'a pas de canal alpha : --transparent n'y aurait aucun effet (l'animation écrit du PNG)
+    let alpha_output = Path::new(output_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("png") || e.eq_ignore_ascii_case("exr"));
+    if transparent && !alpha_output && option("--frames").is_none() {
+        panic!("--transparent demande une sortie .png ou .exr (--output), le PPM n'a pas de canal alpha");
     }
-}
-fn main() -> std::io::Result<()> {
-    let width = 800;
-    let height = 600;
-    let samples = 10;
-    let mut file = File::create("world_scene.ppm")?;
-    writeln!(file, "P3\n{} {}\n255", width, height)?;
-    // Creation de la barre de progression
-    let progress_bar = ProgressBar::new((height * width) as u64);
-    progress_bar.set_style(
-        ProgressStyle::default_bar()
-            .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} pixels ({eta})")
-            .unwrap()
-            .progress_chars("=>-"),
-    );
+    // Région de l'image (--region x y largeur hauteur, depuis le coin haut gauche) : seule cette
+    // partie est calculée ; --crop n'écrit qu'elle, sinon l'image entière avec le reste vide.
+    // --overscan N ajoute N pixels de chaque côté, au-delà du cadre de la caméra
+    let region = option("--region").map(|index| {
+        let value = |offset: usize| {
+            args.get(index + offset)
+                .and_then(|a| a.parse::<usize>().ok())
+                .expect("--region attend x y largeur hauteur")
+        };
+        Region::new(value(1), value(2), value(3), value(4))
+    });
+    let overscan = option("--overscan")
+        .and_then(|index| args.get(index + 1))
+        .map(|a| a.parse::<usize>().expect("--overscan attend un nombre de pixels"))
+        .unwrap_or(0);
+    // La région est ramenée à l'image calculée (débordement compris)
+    let region = region.map(|requested| {
+        let (image_width, image_height) = (width + 2 * overscan, height + 2 * overscan);
+        let region = requested
+            .clamp(image_width, image_height)
+            .unwrap_or_else(|| panic!("--region en dehors de l'image {}×{}", image_width, image_height));
+        if region != requested {
+            eprintln!(
+                "attention : région ramenée à {} {} {} {} pour tenir dans l'image {}×{}",
+                region.x, region.y, region.width, region.height, image_width, image_height
+            );
+        }
+        region
+    });
+    let settings = RenderSettings {
+        width,
+        height,
+        samples,
+        seed,
+        sampler,
+        adaptive,
+        filter,
+        aovs: aov_output.is_some() || denoiser.is_some(),
+        transparent,
+        region,
+        overscan,
+    };
+    // Image telle qu'elle est écrite : découpée à la région avec --crop
+    let crop = option("--crop").is_some();
+    let finish = |image: Image| match region {
+        Some(region) if crop => image.crop(region.x, region.y, region.width, region.height),
+        _ => image,
+    };
+    let finish_aovs = |aovs: &AovBuffers| match region {
+        Some(region) if crop => aovs.crop(region.x, region.y, region.width, region.height),
+        _ => aovs.clone(),
+    };
 
     // Création du World et ajout des objets
     let mut world = World::new();
-    // Ajout de plans
-    world.add(Box::new(Plane::new(
+    // Ajout de plans ; avec --shadow-catcher le sol ne garde que les ombres, pour l'incrustation
+    // Les objets passent par try_new : une géométrie invalide arrête le programme avec son erreur
+    let floor = Plane::try_new(
         Vec3::new(0.0, -0.5, 0.0),
         Vec3::new(0.0, 1.0, 0.0),
         Vec3::new(0.8, 0.8, 0.8),
-    )));
+    )?;
+    if option("--shadow-catcher").is_some() {
+        world.add(Box::new(floor.with_material(Material::shadow_catcher())));
+    } else {
+        world.add(Box::new(floor));
+    }
     // ajout du plan orienter YZ
-    let wall = Plane::new(
+    let wall = Plane::try_new(
         Vec3::new(-4.0, 0.0, 0.0),  // Point sur le plan (mur positionné à x = -4.0)
         Vec3::new(1.0, 0.0, 0.0),   // Normal du plan (pointant vers la droite)
         Vec3::new(0.8, 0.3, 0.3),   // Couleur (rouge pâle)
-    );
+    )?;
     world.add(Box::new(wall));
     
     // // Ajout de plusieurs sphères
@@ -73,38 +172,47 @@ fn main() -> std::io::Result<()> {
     //     0.5,
     //     Vec3::new(0.8, 0.3, 0.3),
     // )));
-    world.add(Box::new(Sphere::new(
-        Vec3::new(1.0, 1.5, 0.0),
-        0.5,
-        Vec3::new(1.0, 1.0, 0.0),
-    )));
+    // Sphère jaune qui rebondit (visible seulement lors du rendu d'une animation)
+    let mut bounce = Track::new(Interpolation::CatmullRom);
+    bounce
+        .add_key(0.0, Vec3::new(0.0, 0.0, 0.0))
+        .add_key(0.5, Vec3::new(0.0, -1.2, 0.0))
+        .add_key(1.0, Vec3::new(0.0, 0.0, 0.0));
+    world.add(Box::new(
+        Animated::new(Box::new(Sphere::try_new(
+            Vec3::new(1.0, 1.5, 0.0),
+            0.5,
+            Vec3::new(1.0, 1.0, 0.0),
+        )?))
+        .with_translation(bounce),
+    ));
 
-    world.add(Box::new(Sphere::new(
+    world.add(Box::new(Sphere::try_new(
         Vec3::new(0.0, 0.0, 0.0),
         0.09,
         Vec3::new(0.0, 0.0, 0.0),
-    )));
+    )?));
 
     // Ajout de cylindres
-    world.add(Box::new(Cylinder::new(
+    world.add(Box::new(Cylinder::try_new(
         Vec3::new(4.0, 0.0, -3.0), // Base
         Vec3::new(1.0, 0.0, 0.0),   // Axe parallèle à Y
         0.5,                        // Rayon
         1.0,                        // Hauteur
         Vec3::new(0.3, 0.3, 0.8),   // Couleur
-    )));
+    )?));
     // Ajout d'un cube avec des dimensions plus visibles
-    let mut cube =  Cube::new(
+    let mut cube =  Cube::try_new(
         Vec3::new(-1.0, -0.5, -2.0),    // Point minimum
         Vec3::new(0.0, 0.5, -1.0),      // Point maximum
         Vec3::new(0.8, 0.6, 0.2),       // Couleur (doré)
-    );
+    )?;
     cube.rotate_y(PI/2.0);
 
     world.add(Box::new(cube));
 
     // Création des lumières
-    let lights = vec![
+    let mut lights = vec![
         Light::new(
             Vec3::new(5.0, 5.0, -5.0), // position de la lumière
             0.8,                       // intensité de la lumière
@@ -112,36 +220,241 @@ fn main() -> std::io::Result<()> {
         // Light::new(Vec3::new(-5.0, 5.0, -3.0), 0.6),
         // Light::new(Vec3::new(0.0, 5.0, 0.0), 0.4),
     ];
- 
 
-    // Définir la caméra 
+    // Fond de la scène : dégradé par défaut, carte HDR (--environment studio.hdr rotation intensité)
+    // ou ciel procédural avec son soleil (--sky élévation azimut turbidité)
+    let value = |index: usize, offset: usize, default: f64| {
+        args.get(index + offset)
+            .and_then(|a| a.parse::<f64>().ok())
+            .unwrap_or(default)
+    };
+    let background: Box<dyn Background> = if let Some(index) = option("--environment") {
+        let path = args.get(index + 1).expect("--environment attend un fichier .hdr ou .exr");
+        Box::new(
+            EnvironmentMap::load(path)?
+                .with_rotation(value(index, 2, 0.0))
+                .with_intensity(value(index, 3, 1.0)),
+        )
+    } else if let Some(index) = option("--sky") {
+        let value = |offset: usize, default: f64| value(index, offset, default);
+        let sky = Sky::new(value(1, 35.0), value(2, 120.0), value(3, 3.0)).with_intensity(0.4);
+        lights.push(sky.sun_light(1.0));
+        Box::new(sky)
+    } else {
+        Box::new(Gradient::default())
+    };
 
-    let camera = Camera::new(
+    // Définir la caméra (--projection perspective|orthographic|fisheye|equisolid|equirectangular|cylindrical)
+    let projection = match option("--projection").and_then(|index| args.get(index + 1)).map(|p| p.as_str()) {
+        None | Some("perspective") => Projection::Perspective { vfov: 60.0 },
+        Some("orthographic") => Projection::Orthographic { view_height: 6.0 },
+        Some("fisheye") => Projection::Fisheye { fov: 180.0, mapping: FisheyeMapping::Equidistant },
+        Some("equisolid") => Projection::Fisheye { fov: 180.0, mapping: FisheyeMapping::Equisolid },
+        Some("equirectangular") => Projection::Equirectangular,
+        Some("cylindrical") => Projection::Cylindrical { horizontal_fov: 200.0, vertical_fov: 60.0 },
+        Some(other) => panic!("projection inconnue : {}", other),
+    };
+    let mut camera_settings = CameraSettings::new(
         Vec3::new(0.1, 1.0, 6.0), // Position de la caméra
         Vec3::new(0.0, 0.0, 0.0),  // Point visé
         Vec3::new(0.0, 1.0, 0.0),  // Vecteur "up"
-        60.0,                      // Champ de vision
         (width as f64) / (height as f64),// ratio
+        projection,
     );
 
-    
-    for j in (0..height).rev() {
-        for i in 0..width {
-            let mut pixel_color = Vec3::new(0.0, 0.0, 0.0);
-            for _ in 0..samples {
-                let u = (i as f64 + rand::random::<f64>()) / (width as f64);
-                let v = (j as f64 + rand::random::<f64>()) / (height as f64);
-                let ray = camera.get_ray(u, v);
-                pixel_color = pixel_color.add(&ray_color(&ray, &world, &lights));
+    // Pixels non carrés (--pixel-aspect largeur/hauteur, par exemple 2 pour un objectif anamorphique)
+    if let Some(pixel_aspect) = option("--pixel-aspect").and_then(|index| args.get(index + 1)) {
+        camera_settings = camera_settings
+            .with_pixel_aspect(pixel_aspect.parse::<f64>().expect("--pixel-aspect attend un rapport"));
+    }
+
+    // Profondeur de champ (--aperture diamètre), mise au point sur l'objet au centre de l'image
+    if let Some(index) = option("--aperture") {
+        camera_settings.aperture = args
+            .get(index + 1)
+            .and_then(|a| a.parse::<f64>().ok())
+            .expect("--aperture attend un diamètre");
+    }
+    let mut scene = Scene::new(world, lights, background)
+        .with_ambient(Ambient::default())
+        .with_shading(Shading::default());
+
+    // Flou de mouvement (--shutter angle) : 180° laisse l'obturateur ouvert la moitié de chaque image
+    let shutter_angle = option("--shutter")
+        .and_then(|index| args.get(index + 1))
+        .map(|a| a.parse::<f64>().expect("--shutter attend un angle en degrés"))
+        .unwrap_or(0.0);
+    scene.set_timeline(scene.timeline.with_shutter_angle(shutter_angle));
+
+    // Objets dégénérés, hors champ ou lumières enfermées : signalés sans arrêter le rendu
+    for issue in scene.validate(&camera_settings) {
+        eprintln!("attention : {}", issue);
+    }
+
+    // Animation (--frames N) : séquence des images 1 à N, pendant laquelle la caméra fait un tour
+    // complet autour du point visé et les objets animés bougent ; les images déjà présentes sur le
+    // disque ne sont pas recalculées
+    if let Some(index) = option("--frames") {
+        // Une seule carte d'AOV ou d'échantillons ne décrirait pas toute la séquence
+        if aov_output.is_some() || option("--heatmap").is_some() {
+            panic!("--aov et --heatmap ne sont pas disponibles avec --frames");
+        }
+        let frames = args
+            .get(index + 1)
+            .and_then(|a| a.parse::<usize>().ok())
+            .filter(|&frames| frames > 0)
+            .expect("--frames attend un nombre d'images");
+        // Cadence (--fps, 24 par défaut) et plage rendue (--start-frame S et --end-frame E, toute la
+        // séquence par défaut) : l'instant d'une image ne dépend que de son numéro
+        let fps = option("--fps")
+            .and_then(|index| args.get(index + 1))
+            .map(|a| a.parse::<f64>().ok().filter(|&fps| fps > 0.0).expect("--fps attend une cadence positive"))
+            .unwrap_or(24.0);
+        let frame_option = |name: &str, default: usize| {
+            option(name)
+                .and_then(|index| args.get(index + 1))
+                .map(|a| a.parse::<usize>().expect("numéro d'image attendu"))
+                .unwrap_or(default)
+        };
+        let start_frame = frame_option("--start-frame", 1);
+        let end_frame = frame_option("--end-frame", frames);
+        assert!(
+            1 <= start_frame && start_frame <= end_frame && end_frame <= frames,
+            "la plage d'images doit vérifier 1 <= --start-frame <= --end-frame <= --frames"
+        );
+        scene.set_timeline(Timeline::new(fps, start_frame, end_frame).with_shutter_angle(shutter_angle));
+        let fov = match projection {
+            Projection::Perspective { vfov } => vfov,
+            _ => 60.0,
+        };
+        let path = CameraPath::turntable(
+            camera_settings.lookat,
+            camera_settings.lookfrom,
+            fov,
+            frames as f64 / fps,
+        );
+        for frame in scene.timeline.frames() {
+            let output = format!("frame_{:04}.png", frame);
+            if Path::new(&output).exists() {
+                continue;
+            }
+            let time = scene.timeline.time(frame);
+            scene.set_time(time);
+            let pose = path.evaluate(time).expect("trajectoire de caméra vide");
+            let camera = camera_settings
+                .with_pose(&pose)
+                .with_shutter(scene.timeline.shutter(frame))
+                .build(&scene.world);
+            // Une graine par image pour que le bruit ne reste pas figé d'une image à l'autre
+            let frame_settings = RenderSettings {
+                seed: seed.wrapping_add(frame as u64),
+                ..settings
+            };
+            let mut output_image = render(&scene, camera.as_ref(), &frame_settings, &progress_bar(frame_settings.sampled_region().pixel_count()));
+            if let (Some(denoiser), Some(aovs)) = (&denoiser, &output_image.aovs) {
+                output_image.image = denoiser.denoise(&output_image.image, aovs);
+            }
+            finish(output_image.image).write_png(&output)?;
+        }
+        return Ok(());
+    }
+
+    let camera = camera_settings
+        .with_shutter(scene.timeline.shutter(scene.timeline.start_frame))
+        .build(&scene.world);
+    // Rendu progressif (--progressive, --time secondes, --noise erreur, --snapshot secondes) :
+    // passes de 4 échantillons jusqu'à 1024 (ou --samples), l'image est réécrite à chaque instantané
+    // et Ctrl-C arrête proprement le rendu en gardant ce qui a été calculé.
+    // --checkpoint fichier enregistre un point de reprise avec chaque instantané et à l'arrêt,
+    // --resume fichier reprend un rendu interrompu là où il s'était arrêté
+    let budget = |name: &str| {
+        option(name)
+            .and_then(|index| args.get(index + 1))
+            .map(|a| a.parse::<f64>().unwrap_or_else(|_| panic!("{} attend un nombre", name)))
+    };
+    let (time_limit, noise_target, snapshot) = (budget("--time"), budget("--noise"), budget("--snapshot"));
+    let path_option = |name: &str| option(name).map(|index| args.get(index + 1).unwrap_or_else(|| panic!("{} attend un fichier", name)));
+    let resume = path_option("--resume");
+    // La reprise continue d'enregistrer dans le fichier repris, sauf si un autre est donné
+    let checkpoint = path_option("--checkpoint").or(resume);
+    let mut output = if option("--progressive").is_some()
+        || resume.is_some()
+        || checkpoint.is_some()
+        || time_limit.is_some()
+        || noise_target.is_some()
+        || snapshot.is_some()
+    {
+        ctrlc::set_handler(|| {
+            // Un second Ctrl-C quitte immédiatement
+            if STOP.swap(true, Ordering::SeqCst) {
+                std::process::exit(130);
             }
-            pixel_color = pixel_color.mul(1.0 / samples as f64);
-            let ir = (255.99 * pixel_color.x.sqrt()) as u8;
-            let ig = (255.99 * pixel_color.y.sqrt()) as u8;
-            let ib = (255.99 * pixel_color.z.sqrt()) as u8;
-            writeln!(file, "{} {} {}", ir, ig, ib).unwrap();
-            // Incrémenter la barre de progression
-            progress_bar.inc(1);
+        })
+        .map_err(std::io::Error::other)?;
+        let mut progressive = Progressive::new(4, explicit_samples.unwrap_or(1024)).with_stop_flag(&STOP);
+        if let Some(seconds) = time_limit {
+            progressive = progressive.with_time_limit(Duration::from_secs_f64(seconds));
         }
+        if let Some(noise) = noise_target {
+            progressive = progressive.with_noise_target(noise);
+        }
+        if let Some(seconds) = snapshot {
+            progressive = progressive.with_snapshots(Duration::from_secs_f64(seconds));
+        }
+        let mut state = match resume {
+            Some(path) => RenderState::load_checkpoint(path, &settings)?,
+            None => RenderState::new(&settings),
+        };
+        let save = |state: &RenderState| -> std::io::Result<()> {
+            if let Some(path) = checkpoint {
+                state.save_checkpoint(path, &settings)?;
+            }
+            finish(state.output().image).write(output_path)
+        };
+        let reason = render_progressive(
+            &scene,
+            camera.as_ref(),
+            &settings,
+            &progressive,
+            &mut state,
+            &mut |state| save(state),
+            &progress_bar(settings.sampled_region().pixel_count()),
+        )?;
+        save(&state)?;
+        eprintln!("Rendu arrêté ({:?}) après {} passes, bruit {:.4}", reason, state.passes, state.noise());
+        state.output()
+    } else {
+        render(&scene, camera.as_ref(), &settings, &progress_bar(settings.sampled_region().pixel_count()))
+    };
+    // Carte du nombre d'échantillons par pixel (--heatmap fichier.png)
+    if let Some(path) = option("--heatmap").and_then(|index| args.get(index + 1)) {
+        finish(output.sample_heatmap()).write(path)?;
     }
-    Ok(())
+    if let (Some(denoiser), Some(aovs)) = (&denoiser, &output.aovs) {
+        output.image = denoiser.denoise(&output.image, aovs);
+    }
+    let image = finish(output.image);
+    // Les AOV sont découpés comme l'image pour que toutes les sorties aient la même taille
+    if let (Some(path), Some(aovs)) = (aov_output, &output.aovs) {
+        if path.ends_with(".exr") {
+            finish_aovs(aovs).write_exr(path, &image)?;
+        } else {
+            for aov in Aov::all(aovs.lights) {
+                finish(aovs.preview(aov)).write(format!("{}_{}.png", path, aov.name()))?;
+            }
+        }
+    }
+    image.write(output_path)
+}
+
+// Creation de la barre de progression
+fn progress_bar(pixels: usize) -> ProgressBar {
+    let progress_bar = ProgressBar::new(pixels as u64);
+    progress_bar.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} pixels ({eta}) {msg}")
+            .unwrap()
+            .progress_chars("=>-"),
+    );
+    progress_bar
 }
@@ -0,0 +1,35 @@
+use crate::config::camera::*;
+use crate::config::ray::*;
+use crate::config::vec3::*;
+
+// Projection cylindrique : angle horizontal proportionnel à u, perspective classique en vertical
+pub struct CylindricalCamera {
+    pub basis: CameraBasis,
+    pub horizontal_fov: f64, // Radians, peut dépasser 180°
+    pub half_height: f64,    // tan(vfov / 2)
+}
+
+impl CylindricalCamera {
+    pub fn new(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        vup: Vec3,
+        horizontal_fov: f64,
+        vertical_fov: f64,
+    ) -> Self {
+        CylindricalCamera {
+            basis: CameraBasis::new(lookfrom, lookat, vup),
+            horizontal_fov: horizontal_fov.to_radians(),
+            half_height: (vertical_fov.to_radians() / 2.0).tan(),
+        }
+    }
+}
+
+impl Camera for CylindricalCamera {
+    fn get_ray_through_lens(&self, u: f64, v: f64, _lens_u: f64, _lens_v: f64) -> Option<Ray> {
+        let angle = (u - 0.5) * self.horizontal_fov;
+        let height = (2.0 * v - 1.0) * self.half_height;
+        let local = Vec3::new(angle.sin(), height, angle.cos());
+        Some(Ray::new(self.basis.origin, self.basis.to_world(&local)))
+    }
+}
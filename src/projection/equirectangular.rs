@@ -0,0 +1,30 @@
+use crate::config::camera::*;
+use crate::config::ray::*;
+use crate::config::vec3::*;
+use std::f64::consts::PI;
+
+// Panorama 360° : u parcourt la longitude (-180° à 180°), v la latitude (-90° à 90°)
+pub struct EquirectangularCamera {
+    pub basis: CameraBasis,
+}
+
+impl EquirectangularCamera {
+    pub fn new(lookfrom: Vec3, lookat: Vec3, vup: Vec3) -> Self {
+        EquirectangularCamera {
+            basis: CameraBasis::new(lookfrom, lookat, vup),
+        }
+    }
+}
+
+impl Camera for EquirectangularCamera {
+    fn get_ray_through_lens(&self, u: f64, v: f64, _lens_u: f64, _lens_v: f64) -> Option<Ray> {
+        let longitude = (u - 0.5) * 2.0 * PI;
+        let latitude = (v - 0.5) * PI;
+        let local = Vec3::new(
+            latitude.cos() * longitude.sin(),
+            latitude.sin(),
+            latitude.cos() * longitude.cos(),
+        );
+        Some(Ray::new(self.basis.origin, self.basis.to_world(&local)))
+    }
+}
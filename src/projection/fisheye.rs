@@ -0,0 +1,56 @@
+use crate::config::camera::*;
+use crate::config::ray::*;
+use crate::config::vec3::*;
+
+// Relation entre l'angle au centre et la distance au centre de l'image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FisheyeMapping {
+    Equidistant, // r proportionnel à θ
+    Equisolid,   // r proportionnel à sin(θ / 2), conserve les aires
+}
+
+// Fisheye circulaire : le cercle image est inscrit dans la hauteur de l'image
+pub struct FisheyeCamera {
+    pub basis: CameraBasis,
+    pub fov: f64, // Champ de vision total sur le diamètre du cercle (radians)
+    pub aspect: f64,
+    pub mapping: FisheyeMapping,
+}
+
+impl FisheyeCamera {
+    pub fn new(
+        lookfrom: Vec3,
+        lookat: Vec3,
+        vup: Vec3,
+        fov: f64,
+        aspect: f64,
+        mapping: FisheyeMapping,
+    ) -> Self {
+        FisheyeCamera {
+            basis: CameraBasis::new(lookfrom, lookat, vup),
+            fov: fov.to_radians(),
+            aspect,
+            mapping,
+        }
+    }
+}
+
+impl Camera for FisheyeCamera {
+    fn get_ray_through_lens(&self, u: f64, v: f64, _lens_u: f64, _lens_v: f64) -> Option<Ray> {
+        let x = (2.0 * u - 1.0) * self.aspect;
+        let y = 2.0 * v - 1.0;
+        let r = (x * x + y * y).sqrt();
+        if r > 1.0 {
+            return None;
+        }
+
+        let half_fov = self.fov / 2.0;
+        let theta = match self.mapping {
+            FisheyeMapping::Equidistant => r * half_fov,
+            FisheyeMapping::Equisolid => 2.0 * (r * (half_fov / 2.0).sin()).clamp(-1.0, 1.0).asin(),
+        };
+        let (sin_phi, cos_phi) = if r > 0.0 { (y / r, x / r) } else { (0.0, 1.0) };
+        let local = Vec3::new(theta.sin() * cos_phi, theta.sin() * sin_phi, theta.cos());
+        Some(Ray::new(self.basis.origin, self.basis.to_world(&local)))
+    }
+}
@@ -0,0 +1,4 @@
+pub mod cylindrical;
+pub mod equirectangular;
+pub mod fisheye;
+pub mod orthographic;
@@ -0,0 +1,29 @@
+use crate::config::camera::*;
+use crate::config::ray::*;
+use crate::config::vec3::*;
+
+// Projection orthographique : rayons parallèles, sans perspective (dessins techniques)
+pub struct OrthographicCamera {
+    pub basis: CameraBasis,
+    pub width: f64,  // Largeur de la zone visible (unités de la scène)
+    pub height: f64, // Hauteur de la zone visible
+}
+
+impl OrthographicCamera {
+    pub fn new(lookfrom: Vec3, lookat: Vec3, vup: Vec3, view_height: f64, aspect: f64) -> Self {
+        OrthographicCamera {
+            basis: CameraBasis::new(lookfrom, lookat, vup),
+            width: view_height * aspect,
+            height: view_height,
+        }
+    }
+}
+
+impl Camera for OrthographicCamera {
+    fn get_ray_through_lens(&self, u: f64, v: f64, _lens_u: f64, _lens_v: f64) -> Option<Ray> {
+        let origin = self.basis.origin
+            + self.basis.u * ((u - 0.5) * self.width)
+            + self.basis.v * ((v - 0.5) * self.height);
+        Some(Ray::new(origin, -self.basis.w))
+    }
+}
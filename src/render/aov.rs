@@ -0,0 +1,374 @@
+use super::image::*;
+use super::sampler::hash;
+use crate::config::material::*;
+use crate::config::vec3::*;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+// Variables de sortie en plus de l'image (AOV), relevées au premier impact de chaque échantillon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aov {
+    Depth,        // Distance le long du rayon primaire
+    Normal,       // Normale en coordonnées monde
+    Albedo,       // Couleur du matériau
+    Position,     // Point touché en coordonnées monde
+    ObjectId,     // Indice de l'objet dans le World
+    MaterialId,   // Identifiant tiré des paramètres du matériau
+    ShadowMask,   // Part moyenne des lumières masquées (0 = éclairé, 1 = dans l'ombre)
+    Light(usize), // Contribution directe d'une lumière de la scène
+}
+
+impl Aov {
+    // Toutes les AOV d'une scène comportant `lights` lumières
+    pub fn all(lights: usize) -> Vec<Aov> {
+        let mut aovs = vec![
+            Aov::Depth,
+            Aov::Normal,
+            Aov::Albedo,
+            Aov::Position,
+            Aov::ObjectId,
+            Aov::MaterialId,
+            Aov::ShadowMask,
+        ];
+        aovs.extend((0..lights).map(Aov::Light));
+        aovs
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Aov::Depth => "depth".to_string(),
+            Aov::Normal => "normal".to_string(),
+            Aov::Albedo => "albedo".to_string(),
+            Aov::Position => "position".to_string(),
+            Aov::ObjectId => "object_id".to_string(),
+            Aov::MaterialId => "material_id".to_string(),
+            Aov::ShadowMask => "shadow".to_string(),
+            Aov::Light(index) => format!("light{}", index),
+        }
+    }
+
+    // Noms des canaux dans un fichier EXR (une seule valeur pour les AOV scalaires)
+    fn channels(&self) -> &'static [&'static str] {
+        match self {
+            Aov::Depth => &["Z"],
+            Aov::ObjectId | Aov::MaterialId | Aov::ShadowMask => &["Y"],
+            Aov::Normal | Aov::Position => &["X", "Y", "Z"],
+            Aov::Albedo | Aov::Light(_) => &["R", "G", "B"],
+        }
+    }
+}
+
+// Valeurs relevées pour un échantillon ; `hit` est faux quand le rayon primaire part dans le fond
+#[derive(Debug, Clone)]
+pub struct AovSample {
+    pub hit: bool,
+    pub depth: f64,
+    pub normal: Vec3,
+    pub albedo: Vec3,
+    pub position: Vec3,
+    pub object: usize,
+    pub material: u64,
+    pub shadow: f64,
+    pub lights: Vec<Vec3>,
+}
+
+impl AovSample {
+    // Échantillon sans impact (rayon primaire dans le fond ou hors du champ)
+    pub fn miss(lights: usize) -> Self {
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        AovSample {
+            hit: false,
+            depth: f64::INFINITY,
+            normal: zero,
+            albedo: zero,
+            position: zero,
+            object: 0,
+            material: 0,
+            shadow: 0.0,
+            lights: vec![zero; lights],
+        }
+    }
+}
+
+// Identifiant stable d'un matériau, calculé à partir de ses paramètres
+pub fn material_id(material: &Material) -> u64 {
+    let alpha = match material.alpha {
+        AlphaMask::Opaque => [0, 0, 0],
+        AlphaMask::Constant(alpha) => [1, alpha.to_bits(), 0],
+        AlphaMask::Checker { scale, alpha } => [2, scale.to_bits(), alpha.to_bits()],
+    };
+    hash(&[
+        material.color.x.to_bits(),
+        material.color.y.to_bits(),
+        material.color.z.to_bits(),
+        material.transmission.to_bits(),
+        material.emission.x.to_bits(),
+        material.emission.y.to_bits(),
+        material.emission.z.to_bits(),
+        alpha[0],
+        alpha[1],
+        alpha[2],
+        material.shadow_catcher as u64,
+    ])
+}
+
+// Accumulation des AOV par pixel. Profondeur, normale et position sont moyennées sur les
+// échantillons qui touchent un objet ; albédo, ombre et lumières sur tous les échantillons ;
+// les identifiants sont ceux du premier échantillon qui touche un objet
+#[derive(Clone)]
+pub struct AovBuffers {
+    pub width: usize,
+    pub height: usize,
+    pub lights: usize,
+    samples: Vec<f64>,
+    hits: Vec<f64>,
+    depth: Vec<f64>,
+    normal: Vec<Vec3>,
+    albedo: Vec<Vec3>,
+    position: Vec<Vec3>,
+    shadow: Vec<f64>,
+    object: Vec<Option<usize>>,
+    material: Vec<Option<u64>>,
+    light: Vec<Vec3>, // `lights` valeurs par pixel
+}
+
+impl AovBuffers {
+    pub fn new(width: usize, height: usize, lights: usize) -> Self {
+        let count = width * height;
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        AovBuffers {
+            width,
+            height,
+            lights,
+            samples: vec![0.0; count],
+            hits: vec![0.0; count],
+            depth: vec![0.0; count],
+            normal: vec![zero; count],
+            albedo: vec![zero; count],
+            position: vec![zero; count],
+            shadow: vec![0.0; count],
+            object: vec![None; count],
+            material: vec![None; count],
+            light: vec![zero; count * lights],
+        }
+    }
+
+    // Partie des AOV qui correspond à `Image::crop` avec les mêmes arguments
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> AovBuffers {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "découpe ({}, {}, {}×{}) hors des AOV {}×{}",
+            x,
+            y,
+            width,
+            height,
+            self.width,
+            self.height
+        );
+        let indices: Vec<usize> = (y..y + height)
+            .flat_map(|row| (x..x + width).map(move |column| row * self.width + column))
+            .collect();
+        fn pick<T: Copy>(values: &[T], indices: &[usize]) -> Vec<T> {
+            indices.iter().map(|&index| values[index]).collect()
+        }
+        AovBuffers {
+            width,
+            height,
+            lights: self.lights,
+            samples: pick(&self.samples, &indices),
+            hits: pick(&self.hits, &indices),
+            depth: pick(&self.depth, &indices),
+            normal: pick(&self.normal, &indices),
+            albedo: pick(&self.albedo, &indices),
+            position: pick(&self.position, &indices),
+            shadow: pick(&self.shadow, &indices),
+            object: pick(&self.object, &indices),
+            material: pick(&self.material, &indices),
+            light: indices
+                .iter()
+                .flat_map(|&index| &self.light[index * self.lights..(index + 1) * self.lights])
+                .copied()
+                .collect(),
+        }
+    }
+
+    // `index` : pixel dans l'ordre de l'image (ligne 0 en haut)
+    pub fn add(&mut self, index: usize, sample: &AovSample) {
+        self.samples[index] += 1.0;
+        if sample.hit {
+            self.hits[index] += 1.0;
+            self.depth[index] += sample.depth;
+            self.normal[index] += sample.normal;
+            self.position[index] += sample.position;
+            self.albedo[index] += sample.albedo;
+            self.shadow[index] += sample.shadow;
+            self.object[index].get_or_insert(sample.object);
+            self.material[index].get_or_insert(sample.material);
+        }
+        for (light, value) in sample.lights.iter().enumerate().take(self.lights) {
+            let slot = index * self.lights + light;
+            self.light[slot] += *value;
+        }
+    }
+
+    // Valeurs de l'AOV pour chaque pixel, sans mise en forme (les scalaires sont répétés sur x, y, z).
+    // Les pixels sans impact ont une profondeur infinie et des identifiants à -1
+    pub fn values(&self, aov: Aov) -> Vec<Vec3> {
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        let scalar = |value: f64| Vec3::new(value, value, value);
+        (0..self.width * self.height)
+            .map(|index| {
+                let hits = self.hits[index];
+                let samples = self.samples[index];
+                match aov {
+                    Aov::Depth if hits > 0.0 => scalar(self.depth[index] / hits),
+                    Aov::Depth => scalar(f64::INFINITY),
+                    Aov::Normal if hits > 0.0 => self.normal[index] * (1.0 / hits),
+                    Aov::Position if hits > 0.0 => self.position[index] * (1.0 / hits),
+                    Aov::Normal | Aov::Position => zero,
+                    Aov::Albedo if samples > 0.0 => self.albedo[index] * (1.0 / samples),
+                    Aov::ShadowMask if samples > 0.0 => scalar(self.shadow[index] / samples),
+                    Aov::Albedo | Aov::ShadowMask => zero,
+                    Aov::ObjectId => scalar(self.object[index].map_or(-1.0, |id| id as f64)),
+                    Aov::MaterialId => {
+                        scalar(self.material[index].map_or(-1.0, |id| (id >> 40) as f64))
+                    }
+                    Aov::Light(light) if light < self.lights && samples > 0.0 => {
+                        self.light[index * self.lights + light] * (1.0 / samples)
+                    }
+                    Aov::Light(_) => zero,
+                }
+            })
+            .collect()
+    }
+
+    // Image lisible de l'AOV : normales ramenées dans [0, 1], profondeur et position normalisées
+    // sur l'image, une couleur par identifiant
+    pub fn preview(&self, aov: Aov) -> Image {
+        let mut values = self.values(aov);
+        // Profondeur en échelle logarithmique pour distinguer le proche comme le lointain
+        if aov == Aov::Depth {
+            for value in &mut values {
+                let depth = value.x.max(1e-6).ln();
+                *value = Vec3::new(depth, depth, depth);
+            }
+        }
+        // Plage des valeurs sans les 2 % extrêmes : un plan infini touché très loin n'écrase pas le reste
+        let mut finite = values
+            .iter()
+            .flat_map(|v| [v.x, v.y, v.z])
+            .filter(|value| value.is_finite())
+            .collect::<Vec<f64>>();
+        finite.sort_by(f64::total_cmp);
+        let percentile = |p: f64| {
+            let index = ((finite.len() as f64 - 1.0) * p).round().max(0.0) as usize;
+            finite.get(index).copied().unwrap_or(0.0)
+        };
+        let (min, max) = (percentile(0.02), percentile(0.98));
+        let range = if max > min { max - min } else { 1.0 };
+        let normalize = |value: f64| ((value - min) / range).clamp(0.0, 1.0);
+
+        let mut image = Image::new(self.width, self.height);
+        for (pixel, value) in image.pixels.iter_mut().zip(&values) {
+            *pixel = match aov {
+                Aov::Normal => *value * 0.5 + Vec3::new(0.5, 0.5, 0.5),
+                // Proche en clair, loin en sombre, fond noir
+                Aov::Depth if value.x.is_finite() => {
+                    let depth = 1.0 - normalize(value.x);
+                    Vec3::new(depth, depth, depth)
+                }
+                Aov::Depth => Vec3::new(0.0, 0.0, 0.0),
+                Aov::Position => {
+                    Vec3::new(normalize(value.x), normalize(value.y), normalize(value.z))
+                }
+                Aov::ObjectId | Aov::MaterialId if value.x < 0.0 => Vec3::new(0.0, 0.0, 0.0),
+                Aov::ObjectId | Aov::MaterialId => id_color(value.x as u64),
+                _ => *value,
+            };
+        }
+        image
+    }
+
+    // Enregistre l'image et toutes les AOV dans un EXR : canaux R, G, B (et A) pour l'image, puis
+    // un groupe de canaux par AOV (depth.Z, normal.X...), en flottants 32 bits
+    pub fn write_exr<P: AsRef<Path>>(&self, path: P, image: &Image) -> io::Result<()> {
+        let mut channels = image.exr_channels();
+        for aov in Aov::all(self.lights) {
+            let values = self.values(aov);
+            for (axis, name) in aov.channels().iter().enumerate() {
+                let samples = values
+                    .iter()
+                    .map(|value| match axis {
+                        0 => value.x as f32,
+                        1 => value.y as f32,
+                        _ => value.z as f32,
+                    })
+                    .collect();
+                channels.push((format!("{}.{}", aov.name(), name), samples));
+            }
+        }
+        write_exr_channels(path, self.width, self.height, channels)
+    }
+
+    // Sommes brutes, en little-endian, pour les points de reprise
+    pub fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        let mut write_f64 = |value: f64| writer.write_all(&value.to_le_bytes());
+        for index in 0..self.width * self.height {
+            for value in [
+                self.samples[index],
+                self.hits[index],
+                self.depth[index],
+                self.shadow[index],
+            ] {
+                write_f64(value)?;
+            }
+            for vector in [self.normal[index], self.albedo[index], self.position[index]] {
+                write_f64(vector.x)?;
+                write_f64(vector.y)?;
+                write_f64(vector.z)?;
+            }
+            // Identifiants : -1 (tous les bits à 1) pour « aucun »
+            write_f64(f64::from_bits(
+                self.object[index].map_or(u64::MAX, |id| id as u64),
+            ))?;
+            write_f64(f64::from_bits(self.material[index].unwrap_or(u64::MAX)))?;
+        }
+        for vector in &self.light {
+            write_f64(vector.x)?;
+            write_f64(vector.y)?;
+            write_f64(vector.z)?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        let mut buffer = [0u8; 8];
+        let mut read_f64 = || -> io::Result<f64> {
+            reader.read_exact(&mut buffer)?;
+            Ok(f64::from_le_bytes(buffer))
+        };
+        let id = |value: f64| Some(value.to_bits()).filter(|bits| *bits != u64::MAX);
+        for index in 0..self.width * self.height {
+            self.samples[index] = read_f64()?;
+            self.hits[index] = read_f64()?;
+            self.depth[index] = read_f64()?;
+            self.shadow[index] = read_f64()?;
+            self.normal[index] = Vec3::new(read_f64()?, read_f64()?, read_f64()?);
+            self.albedo[index] = Vec3::new(read_f64()?, read_f64()?, read_f64()?);
+            self.position[index] = Vec3::new(read_f64()?, read_f64()?, read_f64()?);
+            self.object[index] = id(read_f64()?).map(|id| id as usize);
+            self.material[index] = id(read_f64()?);
+        }
+        for vector in &mut self.light {
+            *vector = Vec3::new(read_f64()?, read_f64()?, read_f64()?);
+        }
+        Ok(())
+    }
+}
+
+// Couleur vive et stable pour un identifiant
+fn id_color(id: u64) -> Vec3 {
+    let bits = hash(&[id]);
+    let channel = |shift: u32| 0.2 + 0.8 * ((bits >> shift) & 0xff) as f64 / 255.0;
+    Vec3::new(channel(0), channel(8), channel(16))
+}
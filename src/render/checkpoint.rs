@@ -0,0 +1,104 @@
+use super::aov::*;
+use super::renderer::*;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Seek, Write};
+use std::path::Path;
+
+// Signature et version du format
+const MAGIC: &[u8; 8] = b"RTCKPT02";
+
+// Point de reprise d'un rendu : film, statistiques des pixels, nombre de passes et AOV. Les nombres
+// aléatoires ne dépendent que de la graine, du pixel et du numéro d'échantillon : le nombre
+// d'échantillons de chaque pixel suffit à retrouver l'état du générateur. Les réglages du rendu
+// sont enregistrés pour refuser une reprise incompatible ; la scène et la caméra ne le sont pas
+impl RenderState {
+    // Écrit dans un fichier temporaire puis le renomme : un arrêt brutal laisse l'ancien point de reprise intact
+    pub fn save_checkpoint<P: AsRef<Path>>(
+        &self,
+        path: P,
+        settings: &RenderSettings,
+    ) -> io::Result<()> {
+        let path = path.as_ref();
+        let temporary = path.with_extension("tmp");
+        {
+            let mut file = BufWriter::new(File::create(&temporary)?);
+            file.write_all(MAGIC)?;
+            let fingerprint = format!("{:?}", settings);
+            file.write_all(&(fingerprint.len() as u64).to_le_bytes())?;
+            file.write_all(fingerprint.as_bytes())?;
+            file.write_all(&(self.passes as u64).to_le_bytes())?;
+            self.film.write_to(&mut file)?;
+            for pixel in &self.pixels {
+                file.write_all(&(pixel.count as u64).to_le_bytes())?;
+                file.write_all(&pixel.mean.to_le_bytes())?;
+                file.write_all(&pixel.m2.to_le_bytes())?;
+            }
+            // Nombre de lumières des AOV plus un, 0 sans AOV
+            match &self.aovs {
+                Some(aovs) => {
+                    file.write_all(&(aovs.lights as u64 + 1).to_le_bytes())?;
+                    aovs.write_to(&mut file)?;
+                }
+                None => file.write_all(&0u64.to_le_bytes())?,
+            }
+            file.flush()?;
+        }
+        fs::rename(&temporary, path)
+    }
+
+    pub fn load_checkpoint<P: AsRef<Path>>(path: P, settings: &RenderSettings) -> io::Result<Self> {
+        let path = path.as_ref();
+        let size = fs::metadata(path)?.len();
+        let mut file = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 8];
+        file.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("ce fichier n'est pas un point de reprise"));
+        }
+        // Les longueurs lues sont vérifiées avant d'allouer : un fichier corrompu ne doit pas
+        // réclamer une taille démesurée
+        let expected = format!("{:?}", settings);
+        let mismatch =
+            || invalid_data("le point de reprise a été créé avec d'autres réglages de rendu");
+        if read_u64(&mut file)? != expected.len() as u64 {
+            return Err(mismatch());
+        }
+        let mut fingerprint = vec![0u8; expected.len()];
+        file.read_exact(&mut fingerprint)?;
+        if fingerprint != expected.as_bytes() {
+            return Err(mismatch());
+        }
+
+        let mut state = RenderState::new(settings);
+        state.passes = read_u64(&mut file)? as usize;
+        state.film.read_from(&mut file)?;
+        for pixel in &mut state.pixels {
+            pixel.count = read_u64(&mut file)? as usize;
+            pixel.mean = f64::from_bits(read_u64(&mut file)?);
+            pixel.m2 = f64::from_bits(read_u64(&mut file)?);
+        }
+        let lights = read_u64(&mut file)?;
+        if lights > 0 {
+            let (width, height) = settings.image_size();
+            // Chaque lumière occupe trois f64 par pixel, qui doivent tenir dans la fin du fichier
+            let remaining = size.saturating_sub(file.stream_position()?);
+            if (lights - 1).saturating_mul(24 * (width * height) as u64) > remaining {
+                return Err(invalid_data("point de reprise tronqué"));
+            }
+            let mut aovs = AovBuffers::new(width, height, lights as usize - 1);
+            aovs.read_from(&mut file)?;
+            state.aovs = Some(aovs);
+        }
+        Ok(state)
+    }
+}
+
+fn read_u64(reader: &mut dyn Read) -> io::Result<u64> {
+    let mut buffer = [0u8; 8];
+    reader.read_exact(&mut buffer)?;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,152 @@
+use super::aov::*;
+use super::image::*;
+use crate::config::vec3::*;
+
+// Noyau B-spline 1D du filtre à trous
+const KERNEL: [f64; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+
+// Débruitage par ondelettes à trous guidé par les AOV (Dammertz et al. 2010, poids de luminance
+// de SVGF) : à chaque itération un noyau 5×5 aux trous espacés de 2^i pixels moyenne les voisins,
+// pondérés par leur ressemblance en normale et en profondeur pour ne pas traverser les contours, et
+// en luminance rapportée au bruit local pour garder les détails d'éclairage. L'éclairage est filtré
+// séparément de l'albédo, ce qui garde les textures nettes
+#[derive(Debug, Clone, Copy)]
+pub struct Denoiser {
+    pub iterations: usize,
+    pub sigma_color: f64,  // Écart de luminance toléré, en écarts types du bruit local
+    pub sigma_normal: f64, // Écart toléré sur 1 - cos(angle) entre les normales
+    pub sigma_depth: f64,  // Écart relatif de profondeur toléré
+}
+
+impl Default for Denoiser {
+    fn default() -> Self {
+        Denoiser {
+            iterations: 5,
+            sigma_color: 4.0,
+            sigma_normal: 0.1,
+            sigma_depth: 0.05,
+        }
+    }
+}
+
+impl Denoiser {
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    // Les AOV doivent avoir été relevés pendant le rendu de l'image (RenderSettings::aovs)
+    pub fn denoise(&self, image: &Image, aovs: &AovBuffers) -> Image {
+        let (width, height) = (image.width, image.height);
+        let albedo = aovs.values(Aov::Albedo);
+        let normal = aovs.values(Aov::Normal);
+        let depth = aovs
+            .values(Aov::Depth)
+            .iter()
+            .map(|value| value.x)
+            .collect::<Vec<f64>>();
+
+        // Éclairage seul : couleur divisée par l'albédo (le fond est filtré tel quel)
+        let demodulate = |index: usize, color: &Vec3, inverse: bool| {
+            if !depth[index].is_finite() {
+                return *color;
+            }
+            let a = albedo[index];
+            let factor = |channel: f64| {
+                let channel = channel.max(0.01);
+                if inverse {
+                    1.0 / channel
+                } else {
+                    channel
+                }
+            };
+            *color * Vec3::new(factor(a.x), factor(a.y), factor(a.z))
+        };
+        let mut current = image
+            .pixels
+            .iter()
+            .enumerate()
+            .map(|(index, color)| demodulate(index, color, true))
+            .collect::<Vec<Vec3>>();
+
+        for iteration in 0..self.iterations {
+            let step = 1isize << iteration;
+            let deviation = local_deviation(&current, &depth, width, height);
+            let mut next = current.clone();
+            for y in 0..height {
+                for x in 0..width {
+                    let index = y * width + x;
+                    let (n, z) = (normal[index], depth[index]);
+                    let luminance = current[index].luminance();
+                    let tolerance = self.sigma_color * deviation[index] + 1e-6;
+                    let mut sum = Vec3::new(0.0, 0.0, 0.0);
+                    let mut total = 0.0;
+                    for (ky, wy) in KERNEL.iter().enumerate() {
+                        let sy = y as isize + (ky as isize - 2) * step;
+                        if sy < 0 || sy >= height as isize {
+                            continue;
+                        }
+                        for (kx, wx) in KERNEL.iter().enumerate() {
+                            let sx = x as isize + (kx as isize - 2) * step;
+                            if sx < 0 || sx >= width as isize {
+                                continue;
+                            }
+                            let other = sy as usize * width + sx as usize;
+                            // Le fond et les objets ne se mélangent pas
+                            let oz = depth[other];
+                            if z.is_finite() != oz.is_finite() {
+                                continue;
+                            }
+                            let mut weight = wx * wy;
+                            weight *= (-(current[other].luminance() - luminance).abs() / tolerance)
+                                .exp();
+                            if z.is_finite() {
+                                let bend = (1.0 - n.dot(&normal[other])).max(0.0);
+                                let relative = (z - oz).abs() / z.max(1e-6);
+                                weight *= (-bend / self.sigma_normal).exp();
+                                weight *= (-relative / (self.sigma_depth * step as f64)).exp();
+                            }
+                            sum += current[other] * weight;
+                            total += weight;
+                        }
+                    }
+                    // Le pixel central a toujours un poids non nul
+                    next[index] = sum * (1.0 / total);
+                }
+            }
+            current = next;
+        }
+
+        let mut output = Image::new(width, height);
+        for (index, pixel) in output.pixels.iter_mut().enumerate() {
+            *pixel = demodulate(index, &current[index], false);
+        }
+        output.alpha = image.alpha.clone();
+        output
+    }
+}
+
+// Écart type de la luminance sur les 3×3 voisins de chaque pixel (fond et objets séparés)
+fn local_deviation(pixels: &[Vec3], depth: &[f64], width: usize, height: usize) -> Vec<f64> {
+    let mut deviation = vec![0.0; pixels.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let index = y * width + x;
+            let (mut sum, mut squares, mut count) = (0.0, 0.0, 0.0);
+            for sy in y.saturating_sub(1)..(y + 2).min(height) {
+                for sx in x.saturating_sub(1)..(x + 2).min(width) {
+                    let other = sy * width + sx;
+                    if depth[other].is_finite() == depth[index].is_finite() {
+                        let luminance = pixels[other].luminance();
+                        sum += luminance;
+                        squares += luminance * luminance;
+                        count += 1.0;
+                    }
+                }
+            }
+            let mean = sum / count;
+            deviation[index] = (squares / count - mean * mean).max(0.0).sqrt();
+        }
+    }
+    deviation
+}
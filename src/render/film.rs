@@ -0,0 +1,119 @@
+use super::filter::*;
+use super::image::*;
+use crate::config::vec3::*;
+use std::io::{self, Read, Write};
+
+// Pellicule : chaque échantillon est réparti sur les pixels voisins selon le filtre de reconstruction,
+// la couleur d'un pixel est la somme pondérée de ses échantillons divisée par la somme des poids
+pub struct Film {
+    pub width: usize,
+    pub height: usize,
+    pub filter: Filter,
+    sums: Vec<Vec3>,
+    weights: Vec<f64>,
+    alphas: Option<Vec<f64>>, // Sommes pondérées de la couverture, pour un fond transparent
+}
+
+impl Film {
+    pub fn new(width: usize, height: usize, filter: Filter) -> Self {
+        Film {
+            width,
+            height,
+            filter,
+            sums: vec![Vec3::new(0.0, 0.0, 0.0); width * height],
+            weights: vec![0.0; width * height],
+            alphas: None,
+        }
+    }
+
+    // Conserve la couverture des échantillons : l'image aura un canal alpha
+    pub fn with_alpha(mut self) -> Self {
+        self.alphas = Some(vec![0.0; self.width * self.height]);
+        self
+    }
+
+    // (x, y) en pixels depuis le coin haut gauche de l'image ; le centre du pixel (i, j) est en (i + 0.5, j + 0.5).
+    // `color` est prémultipliée par `alpha`, la couverture de l'échantillon (1 sans fond transparent)
+    pub fn add_sample(&mut self, x: f64, y: f64, color: &Vec3, alpha: f64) {
+        let radius = self.filter.radius();
+        let x0 = (x - 0.5 - radius).ceil().max(0.0) as usize;
+        let y0 = (y - 0.5 - radius).ceil().max(0.0) as usize;
+        let x1 = ((x - 0.5 + radius).floor() as i64).min(self.width as i64 - 1);
+        let y1 = ((y - 0.5 + radius).floor() as i64).min(self.height as i64 - 1);
+        if x1 < 0 || y1 < 0 {
+            return;
+        }
+
+        for j in y0..=y1 as usize {
+            for i in x0..=x1 as usize {
+                let weight = self.filter.evaluate(i as f64 + 0.5 - x, j as f64 + 0.5 - y);
+                if weight != 0.0 {
+                    let index = j * self.width + i;
+                    self.sums[index] += *color * weight;
+                    self.weights[index] += weight;
+                    if let Some(alphas) = &mut self.alphas {
+                        alphas[index] += alpha * weight;
+                    }
+                }
+            }
+        }
+    }
+
+    // Les lobes négatifs (Mitchell, Lanczos) peuvent donner des valeurs négatives : elles sont ramenées à 0
+    pub fn to_image(&self) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        for (pixel, (sum, weight)) in image
+            .pixels
+            .iter_mut()
+            .zip(self.sums.iter().zip(&self.weights))
+        {
+            if *weight > 0.0 {
+                let color = *sum * (1.0 / weight);
+                *pixel = Vec3::new(color.x.max(0.0), color.y.max(0.0), color.z.max(0.0));
+            }
+        }
+        image.alpha = self.alphas.as_ref().map(|alphas| {
+            alphas
+                .iter()
+                .zip(&self.weights)
+                .map(|(alpha, weight)| {
+                    if *weight > 0.0 {
+                        (alpha / weight).clamp(0.0, 1.0)
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        });
+        image
+    }
+
+    // Sommes et poids bruts, en little-endian, pour les points de reprise
+    pub fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
+        for (sum, weight) in self.sums.iter().zip(&self.weights) {
+            for value in [sum.x, sum.y, sum.z, *weight] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        for alpha in self.alphas.iter().flatten() {
+            writer.write_all(&alpha.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(&mut self, reader: &mut dyn Read) -> io::Result<()> {
+        let mut buffer = [0u8; 8];
+        let mut read_f64 = |reader: &mut dyn Read| -> io::Result<f64> {
+            reader.read_exact(&mut buffer)?;
+            Ok(f64::from_le_bytes(buffer))
+        };
+        for (sum, weight) in self.sums.iter_mut().zip(self.weights.iter_mut()) {
+            *sum = Vec3::new(read_f64(reader)?, read_f64(reader)?, read_f64(reader)?);
+            *weight = read_f64(reader)?;
+        }
+        for alpha in self.alphas.iter_mut().flatten() {
+            *alpha = read_f64(reader)?;
+        }
+        Ok(())
+    }
+}
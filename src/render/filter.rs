@@ -0,0 +1,120 @@
+use std::f64::consts::PI;
+
+// Filtre de reconstruction : poids d'un échantillon selon son écart (en pixels) au centre d'un pixel.
+// Un échantillon contribue à tous les pixels dont le centre est à moins de `radius`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    Box { radius: f64 },
+    Tent { radius: f64 },
+    Gaussian { radius: f64, sigma: f64 },
+    // B = C = 1/3 recommandés par Mitchell et Netravali
+    Mitchell { radius: f64, b: f64, c: f64 },
+    // Sinus cardinal fenêtré par un sinus cardinal plus large (Lanczos-a avec a = radius)
+    Lanczos { radius: f64 },
+}
+
+impl Default for Filter {
+    // Moyenne des échantillons de chaque pixel, sans débordement sur les voisins
+    fn default() -> Self {
+        Filter::Box { radius: 0.5 }
+    }
+}
+
+impl Filter {
+    // Filtres avec leurs réglages usuels
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "box" => Some(Filter::Box { radius: 0.5 }),
+            "tent" | "triangle" => Some(Filter::Tent { radius: 1.0 }),
+            "gaussian" => Some(Filter::Gaussian {
+                radius: 1.5,
+                sigma: 0.5,
+            }),
+            "mitchell" => Some(Filter::Mitchell {
+                radius: 2.0,
+                b: 1.0 / 3.0,
+                c: 1.0 / 3.0,
+            }),
+            "lanczos" => Some(Filter::Lanczos { radius: 3.0 }),
+            _ => None,
+        }
+    }
+
+    pub fn radius(&self) -> f64 {
+        match *self {
+            Filter::Box { radius }
+            | Filter::Tent { radius }
+            | Filter::Gaussian { radius, .. }
+            | Filter::Mitchell { radius, .. }
+            | Filter::Lanczos { radius } => radius,
+        }
+    }
+
+    pub fn with_radius(mut self, value: f64) -> Self {
+        match &mut self {
+            Filter::Box { radius }
+            | Filter::Tent { radius }
+            | Filter::Gaussian { radius, .. }
+            | Filter::Mitchell { radius, .. }
+            | Filter::Lanczos { radius } => *radius = value.max(0.5),
+        }
+        self
+    }
+
+    // Filtre séparable : produit des poids selon x et selon y
+    pub fn evaluate(&self, dx: f64, dy: f64) -> f64 {
+        self.evaluate_1d(dx) * self.evaluate_1d(dy)
+    }
+
+    fn evaluate_1d(&self, x: f64) -> f64 {
+        let x = x.abs();
+        match *self {
+            Filter::Box { radius } => {
+                if x <= radius {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Filter::Tent { radius } => (radius - x).max(0.0),
+            // Décalée pour s'annuler au bord du rayon
+            Filter::Gaussian { radius, sigma } => {
+                let gaussian = |x: f64| (-x * x / (2.0 * sigma * sigma)).exp();
+                (gaussian(x) - gaussian(radius)).max(0.0)
+            }
+            Filter::Mitchell { radius, b, c } => mitchell(2.0 * x / radius, b, c),
+            Filter::Lanczos { radius } => {
+                if x >= radius {
+                    0.0
+                } else {
+                    sinc(x) * sinc(x / radius)
+                }
+            }
+        }
+    }
+}
+
+// Polynôme cubique par morceaux de Mitchell-Netravali, défini sur [0, 2]
+fn mitchell(x: f64, b: f64, c: f64) -> f64 {
+    let value = if x > 2.0 {
+        0.0
+    } else if x > 1.0 {
+        (-b - 6.0 * c) * x * x * x
+            + (6.0 * b + 30.0 * c) * x * x
+            + (-12.0 * b - 48.0 * c) * x
+            + (8.0 * b + 24.0 * c)
+    } else {
+        (12.0 - 9.0 * b - 6.0 * c) * x * x * x
+            + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+            + (6.0 - 2.0 * b)
+    };
+    value / 6.0
+}
+
+fn sinc(x: f64) -> f64 {
+    if x < 1e-5 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::config::vec3::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+// Image en couleurs linéaires, la ligne 0 est en haut
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Vec3>,
+    // Couverture de chaque pixel (fond transparent) ; les couleurs sont alors prémultipliées
+    pub alpha: Option<Vec<f64>>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![Vec3::new(0.0, 0.0, 0.0); width * height],
+            alpha: None,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Vec3 {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Vec3) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    // Rectangle (x, y, largeur, hauteur) de l'image, depuis le coin haut gauche
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Image {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "découpe ({}, {}, {}×{}) hors d'une image {}×{}",
+            x,
+            y,
+            width,
+            height,
+            self.width,
+            self.height
+        );
+        let mut image = Image::new(width, height);
+        let rows = (y..y + height).flat_map(|row| (x..x + width).map(move |column| (row, column)));
+        let indices = rows
+            .map(|(row, column)| row * self.width + column)
+            .collect::<Vec<usize>>();
+        image.pixels = indices.iter().map(|&index| self.pixels[index]).collect();
+        image.alpha = self
+            .alpha
+            .as_ref()
+            .map(|alpha| indices.iter().map(|&index| alpha[index]).collect());
+        image
+    }
+
+    // PPM texte avec correction gamma 2
+    pub fn write_ppm<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "P3\n{} {}\n255", self.width, self.height)?;
+        for color in &self.pixels {
+            let [r, g, b] = to_rgb8(color);
+            writeln!(file, "{} {} {}", r, g, b)?;
+        }
+        file.flush()
+    }
+
+    // PNG 8 bits avec correction gamma 2 : RGB, ou RGBA avec un alpha non prémultiplié comme le veut
+    // le format
+    pub fn write_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_depth(png::BitDepth::Eight);
+        let data: Vec<u8> = match &self.alpha {
+            Some(alpha) => {
+                encoder.set_color(png::ColorType::Rgba);
+                self.pixels
+                    .iter()
+                    .zip(alpha)
+                    .flat_map(|(color, &alpha)| {
+                        let straight = if alpha > 0.0 { *color * (1.0 / alpha) } else { *color };
+                        let [r, g, b] = to_rgb8(&straight);
+                        [r, g, b, (255.99 * alpha.clamp(0.0, 1.0)) as u8]
+                    })
+                    .collect()
+            }
+            None => {
+                encoder.set_color(png::ColorType::Rgb);
+                self.pixels.iter().flat_map(to_rgb8).collect()
+            }
+        };
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&data).map_err(io::Error::other)
+    }
+
+    // EXR en flottants linéaires : R, G, B, et A (prémultiplié) pour un fond transparent
+    pub fn write_exr<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        write_exr_channels(path, self.width, self.height, self.exr_channels())
+    }
+
+    pub(crate) fn exr_channels(&self) -> Vec<(String, Vec<f32>)> {
+        let mut channels = vec![
+            ("R".to_string(), self.pixels.iter().map(|c| c.x as f32).collect()),
+            ("G".to_string(), self.pixels.iter().map(|c| c.y as f32).collect()),
+            ("B".to_string(), self.pixels.iter().map(|c| c.z as f32).collect()),
+        ];
+        if let Some(alpha) = &self.alpha {
+            channels.push(("A".to_string(), alpha.iter().map(|&a| a as f32).collect()));
+        }
+        channels
+    }
+
+    // Format choisi selon l'extension (.png, .exr, sinon PPM)
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("png") => self.write_png(path),
+            Some(extension) if extension.eq_ignore_ascii_case("exr") => self.write_exr(path),
+            _ => self.write_ppm(path),
+        }
+    }
+}
+
+// Écrit un EXR d'une seule couche à partir de canaux nommés (un flottant par pixel)
+pub(crate) fn write_exr_channels<P: AsRef<Path>>(
+    path: P,
+    width: usize,
+    height: usize,
+    channels: Vec<(String, Vec<f32>)>,
+) -> io::Result<()> {
+    use exr::prelude::*;
+
+    let channels = channels
+        .into_iter()
+        .map(|(name, samples)| AnyChannel::new(name.as_str(), FlatSamples::F32(samples)))
+        .collect::<Vec<_>>();
+    let layer = Layer::new(
+        (width, height),
+        LayerAttributes::named("rt"),
+        Encoding::FAST_LOSSLESS,
+        AnyChannels::sort(SmallVec::from_vec(channels)),
+    );
+    exr::prelude::Image::from_layer(layer)
+        .write()
+        .to_file(path)
+        .map_err(io::Error::other)
+}
+
+// Couleur linéaire -> 8 bits avec correction gamma 2
+pub fn to_rgb8(color: &Vec3) -> [u8; 3] {
+    [
+        (255.99 * color.x.sqrt()) as u8,
+        (255.99 * color.y.sqrt()) as u8,
+        (255.99 * color.z.sqrt()) as u8,
+    ]
+}
@@ -0,0 +1,9 @@
+pub mod image;
+pub mod renderer;
+pub mod sampler;
+pub mod film;
+pub mod filter;
+pub mod progressive;
+pub mod checkpoint;
+pub mod aov;
+pub mod denoise;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use super::renderer::*;
+use crate::config::camera::*;
+use crate::config::scene::*;
+use indicatif::ProgressBar;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// Rendu progressif : des passes successives sur toute l'image ajoutent `samples_per_pass`
+// échantillons à chaque pixel, jusqu'à `max_samples` ou jusqu'à épuisement d'un budget
+#[derive(Debug, Clone, Copy)]
+pub struct Progressive {
+    pub samples_per_pass: usize,
+    pub max_samples: usize,
+    pub time_limit: Option<Duration>,
+    pub noise_target: Option<f64>, // Erreur relative moyenne de l'image (voir RenderState::noise)
+    pub snapshot_interval: Option<Duration>,
+    pub stop: Option<&'static AtomicBool>, // Arrêt demandé de l'extérieur (Ctrl-C)
+}
+
+impl Progressive {
+    pub fn new(samples_per_pass: usize, max_samples: usize) -> Self {
+        let samples_per_pass = samples_per_pass.max(1);
+        Progressive {
+            samples_per_pass,
+            max_samples: max_samples.max(samples_per_pass),
+            time_limit: None,
+            noise_target: None,
+            snapshot_interval: None,
+            stop: None,
+        }
+    }
+
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    pub fn with_noise_target(mut self, noise_target: f64) -> Self {
+        self.noise_target = Some(noise_target);
+        self
+    }
+
+    pub fn with_snapshots(mut self, interval: Duration) -> Self {
+        self.snapshot_interval = Some(interval);
+        self
+    }
+
+    pub fn with_stop_flag(mut self, stop: &'static AtomicBool) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    fn stop_requested(&self) -> bool {
+        self.stop.is_some_and(|stop| stop.load(Ordering::SeqCst))
+    }
+}
+
+// Raison de l'arrêt d'un rendu progressif
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason {
+    Completed,
+    TimeLimit,
+    NoiseTarget,
+    Interrupted, // Le drapeau d'arrêt a été levé (Ctrl-C)
+}
+
+// Enchaîne les passes sur `state` ; `snapshot` reçoit l'état après chaque passe lorsque l'intervalle
+// des instantanés est écoulé. L'arrêt (temps ou drapeau) est pris en compte à chaque ligne, l'état
+// reste utilisable pour produire l'image de ce qui a été calculé
+pub fn render_progressive(
+    scene: &Scene,
+    camera: &dyn Camera,
+    settings: &RenderSettings,
+    progressive: &Progressive,
+    state: &mut RenderState,
+    snapshot: &mut dyn FnMut(&RenderState) -> io::Result<()>,
+    progress: &ProgressBar,
+) -> io::Result<StopReason> {
+    let start = Instant::now();
+    let mut last_snapshot = start;
+    let out_of_time = || progressive.time_limit.is_some_and(|limit| start.elapsed() >= limit);
+
+    loop {
+        if let Some(noise_target) = progressive.noise_target {
+            if state.passes > 0 && state.noise() <= noise_target {
+                return Ok(StopReason::NoiseTarget);
+            }
+        }
+        let done = state.passes * progressive.samples_per_pass;
+        if done >= progressive.max_samples {
+            return Ok(StopReason::Completed);
+        }
+
+        let target = (done + progressive.samples_per_pass).min(progressive.max_samples);
+        progress.reset();
+        progress.set_message(format!("passe {}", state.passes + 1));
+        let interrupted = || progressive.stop_requested() || out_of_time();
+        if !render_pass(scene, camera, settings, state, target, &interrupted, progress) {
+            return Ok(if progressive.stop_requested() {
+                StopReason::Interrupted
+            } else {
+                StopReason::TimeLimit
+            });
+        }
+        state.passes += 1;
+
+        if let Some(interval) = progressive.snapshot_interval {
+            if last_snapshot.elapsed() >= interval {
+                snapshot(state)?;
+                last_snapshot = Instant::now();
+            }
+        }
+    }
+}
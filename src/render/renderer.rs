@@ -0,0 +1,442 @@
+use super::aov::*;
+use super::film::*;
+use super::filter::*;
+use super::image::*;
+use super::sampler::*;
+use crate::config::camera::*;
+use crate::config::light::*;
+use crate::config::ray::*;
+use crate::config::scene::*;
+use crate::config::vec3::*;
+use indicatif::ProgressBar;
+
+// Réglages de l'image à produire
+#[derive(Debug, Clone, Copy)]
+pub struct RenderSettings {
+    pub width: usize,
+    pub height: usize,
+    pub samples: usize, // Échantillons par pixel pour l'anti-aliasing
+    pub seed: u64,      // Graine des nombres aléatoires : même graine, même image
+    pub sampler: SamplerKind,
+    pub adaptive: Option<AdaptiveSampling>, // Remplace `samples` lorsqu'il est présent
+    pub filter: Filter,
+    pub aovs: bool, // Relève aussi les AOV (profondeur, normales, albédo...) des rayons primaires
+    pub transparent: bool, // Fond transparent : l'image a un canal alpha et des couleurs prémultipliées
+    pub region: Option<Region>, // Seule partie de l'image calculée, le reste est laissé vide
+    pub overscan: usize, // Pixels ajoutés de chaque côté de l'image, au-delà du cadre de la caméra
+}
+
+impl RenderSettings {
+    // Taille de l'image produite, marges de débordement comprises
+    pub fn image_size(&self) -> (usize, usize) {
+        (self.width + 2 * self.overscan, self.height + 2 * self.overscan)
+    }
+
+    // Pixels à échantillonner : la région agrandie du rayon du filtre, pour que ses bords reçoivent
+    // les mêmes échantillons voisins que dans un rendu complet
+    pub fn sampled_region(&self) -> Region {
+        let (width, height) = self.image_size();
+        match self.region {
+            Some(region) => region.expand(self.filter.radius().ceil() as usize, width, height),
+            None => Region::new(0, 0, width, height),
+        }
+    }
+}
+
+// Rectangle de pixels, depuis le coin haut gauche de l'image
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Region {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Region {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    pub fn pixel_count(&self) -> usize {
+        self.width * self.height
+    }
+
+    // Partie du rectangle qui tient dans une image de la taille donnée ; None si elle est vide
+    pub fn clamp(&self, width: usize, height: usize) -> Option<Region> {
+        let region = self.expand(0, width, height);
+        (region.pixel_count() > 0).then_some(region)
+    }
+
+    // Rectangle agrandi de `margin` pixels de chaque côté, limité à une image de la taille donnée
+    pub fn expand(&self, margin: usize, width: usize, height: usize) -> Region {
+        let x = self.x.saturating_sub(margin).min(width);
+        let y = self.y.saturating_sub(margin).min(height);
+        let right = (self.x + self.width + margin).min(width);
+        let bottom = (self.y + self.height + margin).min(height);
+        Region::new(x, y, right.saturating_sub(x), bottom.saturating_sub(y))
+    }
+}
+
+// Échantillonnage adaptatif : un pixel s'arrête dès que l'erreur estimée sur sa luminance moyenne
+// passe sous le seuil, entre `min_samples` et `max_samples` échantillons
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveSampling {
+    pub min_samples: usize,
+    pub max_samples: usize,
+    pub threshold: f64, // Erreur relative tolérée : écart type de la moyenne / luminance
+}
+
+impl AdaptiveSampling {
+    pub fn new(min_samples: usize, max_samples: usize, threshold: f64) -> Self {
+        let min_samples = min_samples.max(2);
+        AdaptiveSampling {
+            min_samples,
+            max_samples: max_samples.max(min_samples),
+            threshold,
+        }
+    }
+
+    pub fn converged(&self, stats: &PixelStats) -> bool {
+        if stats.count < self.min_samples {
+            return false;
+        }
+        stats.count >= self.max_samples || stats.relative_error() <= self.threshold
+    }
+}
+
+// Image rendue et nombre d'échantillons calculés pour chaque pixel (même ordre que les pixels)
+pub struct RenderOutput {
+    pub image: Image,
+    pub sample_counts: Vec<usize>,
+    pub aovs: Option<AovBuffers>,
+}
+
+impl RenderOutput {
+    // Carte des échantillons : bleu pour le minimum de l'image, vert, puis rouge pour le maximum
+    pub fn sample_heatmap(&self) -> Image {
+        let min = self.sample_counts.iter().copied().min().unwrap_or(0);
+        let max = self.sample_counts.iter().copied().max().unwrap_or(0);
+        let mut heatmap = Image::new(self.image.width, self.image.height);
+        for (pixel, &count) in heatmap.pixels.iter_mut().zip(&self.sample_counts) {
+            let t = if max > min {
+                (count - min) as f64 / (max - min) as f64
+            } else {
+                0.0
+            };
+            *pixel = Vec3::new(
+                (2.0 * t - 1.0).max(0.0),
+                1.0 - (2.0 * t - 1.0).abs(),
+                (1.0 - 2.0 * t).max(0.0),
+            );
+        }
+        heatmap
+    }
+}
+
+pub fn ray_color(ray: &Ray, scene: &Scene, sampler: &mut dyn Sampler) -> Vec3 {
+    trace(ray, scene, sampler, None, false).0
+}
+
+// Couleur prémultipliée et couverture d'un rayon ; avec `transparent`, le fond a une couverture
+// nulle au lieu de sa couleur. Les AOV éventuels sont relevés au premier impact
+fn trace(
+    ray: &Ray,
+    scene: &Scene,
+    sampler: &mut dyn Sampler,
+    mut aov: Option<&mut AovSample>,
+    transparent: bool,
+) -> (Vec3, f64) {
+    let world = &scene.world;
+    let shading = &scene.shading;
+    match world.hit_object(ray, 0.001, f64::INFINITY) {
+        Some((object, hit_record)) => {
+            if let Some(aov) = aov.as_deref_mut() {
+                aov.hit = true;
+                aov.depth = hit_record.t;
+                aov.normal = hit_record.normal;
+                aov.albedo = hit_record.color;
+                aov.position = hit_record.point;
+                aov.object = object;
+                aov.material = material_id(&hit_record.material);
+            }
+
+            // Attrape-ombres : ce qui se trouve derrière, assombri par l'ombre reçue. En
+            // prémultiplié, c'est une couche noire de couverture égale à l'ombre
+            if hit_record.material.shadow_catcher {
+                let shadow = shadow_fraction(&hit_record, &scene.lights, world, shading, ray.time);
+                if let Some(aov) = aov {
+                    aov.shadow = shadow;
+                }
+                let behind = Ray::new(hit_record.point, ray.direction).at_time(ray.time);
+                let (behind_color, behind_alpha) = trace(&behind, scene, sampler, None, transparent);
+                return (
+                    behind_color * (1.0 - shadow),
+                    shadow + behind_alpha * (1.0 - shadow),
+                );
+            }
+
+            // L'ambiant est compté une seule fois, chaque lumière ajoute sa contribution directe
+            let mut total_color = scene.ambient.contribution(&hit_record);
+
+            // Lumière émise par l'objet lui-même
+            total_color += hit_record.material.emission;
+
+            for (index, light) in scene.lights.iter().enumerate() {
+                // Passage du world en paramètre
+                let light_color =
+                    calculate_lighting(&hit_record, ray, light, world, shading);
+                total_color += light_color;
+
+                if let Some(aov) = aov.as_deref_mut() {
+                    aov.lights[index] = light_color;
+                }
+            }
+            // Masque d'ombre : même définition que pour le receveur d'ombres
+            if let Some(aov) = aov {
+                aov.shadow = shadow_fraction(&hit_record, &scene.lights, world, shading, ray.time);
+            }
+
+            // Éclairage par l'environnement (sans effet pour un fond qui n'éclaire pas)
+            let environment_samples = shading.environment_samples.max(1);
+            for _ in 0..environment_samples {
+                let (u, v) = sampler.next_2d();
+                let light_color = environment_lighting(
+                    &hit_record,
+                    scene.background.as_ref(),
+                    world,
+                    shading,
+                    ray.time,
+                    u,
+                    v,
+                );
+                total_color += light_color * (1.0 / environment_samples as f64);
+            }
+
+            // Éclairage par les objets émissifs
+            if !scene.emitters.is_empty() {
+                let emitter_samples = shading.emitter_samples.max(1);
+                for _ in 0..emitter_samples {
+                    let select = sampler.next_1d();
+                    let (u, v) = sampler.next_2d();
+                    let sample = (select, u, v);
+                    let light_color = emitter_lighting(
+                        &hit_record,
+                        &scene.emitters,
+                        world,
+                        shading,
+                        ray.time,
+                        sample,
+                    );
+                    total_color += light_color * (1.0 / emitter_samples as f64);
+                }
+            }
+
+            // Surface partiellement découpée : on mélange avec ce qui se trouve derrière
+            let coverage = hit_record.material.alpha.coverage(&hit_record.point);
+            let mut alpha = 1.0;
+            if coverage < 1.0 {
+                let behind = Ray::new(hit_record.point, ray.direction).at_time(ray.time);
+                let (behind_color, behind_alpha) = trace(&behind, scene, sampler, None, transparent);
+                total_color = total_color * coverage + behind_color * (1.0 - coverage);
+                alpha = coverage + behind_alpha * (1.0 - coverage);
+            }
+
+            (total_color, alpha)
+        }
+        None if transparent => (Vec3::new(0.0, 0.0, 0.0), 0.0),
+        None => (scene.background.radiance(&ray.direction), 1.0),
+    }
+}
+
+// Statistiques d'un pixel : nombre d'échantillons, moyenne et somme des carrés des écarts de la
+// luminance (méthode de Welford)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PixelStats {
+    pub count: usize,
+    pub mean: f64,
+    pub m2: f64,
+}
+
+impl PixelStats {
+    pub fn add(&mut self, luminance: f64) {
+        self.count += 1;
+        let delta = luminance - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (luminance - self.mean);
+    }
+
+    // Écart type estimé de la moyenne, rapporté à la luminance (plancher pour les pixels presque noirs)
+    pub fn relative_error(&self) -> f64 {
+        if self.count < 2 {
+            return f64::INFINITY;
+        }
+        let count = self.count as f64;
+        (self.m2 / (count - 1.0) / count).sqrt() / self.mean.max(0.01)
+    }
+}
+
+// Rendu en cours : film et statistiques de chaque pixel (même ordre que les pixels de l'image)
+pub struct RenderState {
+    pub film: Film,
+    pub pixels: Vec<PixelStats>,
+    pub passes: usize, // Passes complètes sur l'image
+    pub aovs: Option<AovBuffers>, // Créés au premier rendu si `RenderSettings::aovs`
+    region: Option<Region>,
+}
+
+impl RenderState {
+    pub fn new(settings: &RenderSettings) -> Self {
+        let (width, height) = settings.image_size();
+        RenderState {
+            film: if settings.transparent {
+                Film::new(width, height, settings.filter).with_alpha()
+            } else {
+                Film::new(width, height, settings.filter)
+            },
+            pixels: vec![PixelStats::default(); width * height],
+            passes: 0,
+            aovs: None,
+            region: settings.region,
+        }
+    }
+
+    // Hors de la région rendue, l'image reste vide (noire, ou transparente avec un canal alpha)
+    pub fn output(&self) -> RenderOutput {
+        let mut output = RenderOutput {
+            image: self.film.to_image(),
+            sample_counts: self.pixels.iter().map(|pixel| pixel.count).collect(),
+            aovs: self.aovs.clone(),
+        };
+        if let Some(region) = self.region {
+            let width = output.image.width;
+            for (index, pixel) in output.image.pixels.iter_mut().enumerate() {
+                if !region.contains(index % width, index / width) {
+                    *pixel = Vec3::new(0.0, 0.0, 0.0);
+                    output.sample_counts[index] = 0;
+                    if let Some(alpha) = &mut output.image.alpha {
+                        alpha[index] = 0.0;
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    // Erreur relative moyenne des pixels de l'image (de la région rendue)
+    pub fn noise(&self) -> f64 {
+        let width = self.film.width;
+        let (total, count) = self
+            .pixels
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                self.region
+                    .is_none_or(|region| region.contains(index % width, index / width))
+            })
+            .fold((0.0, 0), |(total, count), (_, pixel)| {
+                (total + pixel.relative_error().min(1.0), count + 1)
+            });
+        total / count.max(1) as f64
+    }
+}
+
+// Rendu complet d'une image ; la barre de progression avance d'un pas par pixel
+pub fn render(
+    scene: &Scene,
+    camera: &dyn Camera,
+    settings: &RenderSettings,
+    progress: &ProgressBar,
+) -> RenderOutput {
+    // Sans adaptatif, chaque pixel reçoit exactement le nombre d'échantillons demandé
+    let max_samples = match settings.adaptive {
+        Some(adaptive) => adaptive.max_samples,
+        None => settings.samples,
+    };
+    let mut state = RenderState::new(settings);
+    render_pass(scene, camera, settings, &mut state, max_samples, &|| false, progress);
+    state.output()
+}
+
+// Amène chaque pixel à `target` échantillons (moins s'il a convergé). `interrupted` est consulté à
+// chaque ligne : la passe s'arrête alors en laissant un état cohérent, qu'une nouvelle passe avec
+// la même cible complètera exactement comme si elle n'avait pas été interrompue. Renvoie false si
+// la passe a été interrompue
+pub fn render_pass(
+    scene: &Scene,
+    camera: &dyn Camera,
+    settings: &RenderSettings,
+    state: &mut RenderState,
+    target: usize,
+    interrupted: &dyn Fn() -> bool,
+    progress: &ProgressBar,
+) -> bool {
+    let (width, height) = settings.image_size();
+    let region = settings.sampled_region();
+    let overscan = settings.overscan as f64;
+    let shutter = camera.shutter();
+    let min_samples = settings.adaptive.map_or(settings.samples, |adaptive| adaptive.min_samples);
+    let mut sampler = settings.sampler.build(settings.seed, min_samples);
+    if settings.aovs && state.aovs.is_none() {
+        state.aovs = Some(AovBuffers::new(width, height, scene.lights.len()));
+    }
+
+    // Lignes de haut en bas ; j compte les lignes depuis le bas, comme v
+    for y in region.y..region.y + region.height {
+        if interrupted() {
+            return false;
+        }
+        let j = height - 1 - y;
+        for i in region.x..region.x + region.width {
+            let index = y * width + i;
+            let mut stats = state.pixels[index];
+            while stats.count < target {
+                if let Some(adaptive) = &settings.adaptive {
+                    if adaptive.converged(&stats) {
+                        break;
+                    }
+                }
+                // Dimensions dans l'ordre : position dans le pixel, point de l'objectif, instant
+                sampler.start_sample(i, j, stats.count);
+                let (jitter_u, jitter_v) = sampler.next_2d();
+                let (lens_u, lens_v) = sampler.next_2d();
+                let time = shutter.time(sampler.next_1d());
+                // Le cadre de la caméra couvre [0, 1] hors débordement
+                let u = (i as f64 - overscan + jitter_u) / (settings.width as f64);
+                let v = (j as f64 - overscan + jitter_v) / (settings.height as f64);
+                // Hors du champ de la projection, le pixel reste noir
+                let ray = camera
+                    .get_ray_through_lens(u, v, lens_u, lens_v)
+                    .map(|ray| ray.at_time(time));
+                let mut aov = state.aovs.as_ref().map(|aovs| AovSample::miss(aovs.lights));
+                let (color, alpha) = match &ray {
+                    Some(ray) => trace(ray, scene, sampler.as_mut(), aov.as_mut(), settings.transparent),
+                    None => (Vec3::new(0.0, 0.0, 0.0), 0.0),
+                };
+                if let (Some(aovs), Some(aov)) = (state.aovs.as_mut(), &aov) {
+                    aovs.add(index, aov);
+                }
+                // Le film compte les lignes depuis le haut
+                state.film.add_sample(
+                    i as f64 + jitter_u,
+                    (height - j) as f64 - jitter_v,
+                    &color,
+                    alpha,
+                );
+                stats.add(color.luminance());
+            }
+            state.pixels[index] = stats;
+            // Incrémenter la barre de progression
+            progress.inc(1);
+        }
+    }
+    true
+}
@@ -0,0 +1,117 @@
+use super::*;
+use std::sync::OnceLock;
+
+// Côté de la tuile de bruit bleu, répétée sur toute l'image
+const SIZE: usize = 64;
+// Écart type (pixels) du noyau qui mesure la densité des points déjà placés
+const SIGMA: f64 = 1.5;
+// Pas des suites d'échantillons : nombre d'or en 1D, suite R2 (nombre plastique) en 2D
+const GOLDEN: f64 = 0.618_033_988_749_894_9;
+const R2: (f64, f64) = (0.754_877_666_246_692_7, 0.569_840_290_998_053_2);
+
+// Bruit bleu : pour un même échantillon, les valeurs de pixels voisins sont aussi différentes que
+// possible, l'erreur se répartit en grain fin plutôt qu'en taches. Chaque dimension lit la tuile à
+// un décalage différent, et les échantillons successifs d'un pixel avancent le long d'une suite à
+// faible discrépance
+#[derive(Debug, Clone)]
+pub struct BlueNoiseSampler {
+    seed: u64,
+    x: usize,
+    y: usize,
+    index: u64,
+    dimension: u64,
+}
+
+impl BlueNoiseSampler {
+    pub fn new(seed: u64) -> Self {
+        BlueNoiseSampler {
+            seed,
+            x: 0,
+            y: 0,
+            index: 0,
+            dimension: 0,
+        }
+    }
+
+    // Valeur de la tuile pour ce pixel, décalée selon la dimension et l'axe, dans [0, 1[
+    fn tile_value(&self, axis: u64) -> f64 {
+        let offset = hash(&[self.seed, self.dimension, axis]);
+        let x = (self.x + (offset as usize % SIZE)) % SIZE;
+        let y = (self.y + ((offset >> 32) as usize % SIZE)) % SIZE;
+        // Position aléatoire dans l'intervalle du rang pour ne pas se limiter à SIZE² valeurs
+        let jitter = to_unit(hash(&[self.seed, self.x as u64, self.y as u64, self.dimension, axis]));
+        (tile()[y * SIZE + x] as f64 + jitter) / (SIZE * SIZE) as f64
+    }
+}
+
+impl Sampler for BlueNoiseSampler {
+    fn start_sample(&mut self, x: usize, y: usize, index: usize) {
+        self.x = x;
+        self.y = y;
+        self.index = index as u64;
+        self.dimension = 0;
+    }
+
+    fn next_1d(&mut self) -> f64 {
+        let value = (self.tile_value(0) + self.index as f64 * GOLDEN).fract();
+        self.dimension += 1;
+        value
+    }
+
+    fn next_2d(&mut self) -> (f64, f64) {
+        let u = (self.tile_value(0) + self.index as f64 * R2.0).fract();
+        let v = (self.tile_value(1) + self.index as f64 * R2.1).fract();
+        self.dimension += 2;
+        (u, v)
+    }
+}
+
+// Tuile calculée une seule fois : rang de chaque pixel dans l'ordre de remplissage
+fn tile() -> &'static [u16] {
+    static TILE: OnceLock<Vec<u16>> = OnceLock::new();
+    TILE.get_or_init(generate_tile)
+}
+
+// Variante de « void and cluster » (Ulichney) : on remplit la tuile point par point, en plaçant
+// chaque nouveau point dans le plus grand vide restant (énergie gaussienne minimale, tuile torique)
+fn generate_tile() -> Vec<u16> {
+    let count = SIZE * SIZE;
+    let mut kernel = vec![0.0; count];
+    for dy in 0..SIZE {
+        for dx in 0..SIZE {
+            let ddx = dx.min(SIZE - dx) as f64;
+            let ddy = dy.min(SIZE - dy) as f64;
+            kernel[dy * SIZE + dx] = (-(ddx * ddx + ddy * ddy) / (2.0 * SIGMA * SIGMA)).exp();
+        }
+    }
+
+    // Une infime perturbation départage les égalités sans introduire de motif régulier
+    let mut energy = (0..count)
+        .map(|i| to_unit(hash(&[i as u64])) * 1e-9)
+        .collect::<Vec<_>>();
+    let mut rank = vec![0u16; count];
+    let mut filled = vec![false; count];
+
+    for r in 0..count {
+        let mut best = 0;
+        let mut best_energy = f64::INFINITY;
+        for (i, &e) in energy.iter().enumerate() {
+            if !filled[i] && e < best_energy {
+                best = i;
+                best_energy = e;
+            }
+        }
+        filled[best] = true;
+        rank[best] = r as u16;
+
+        let (bx, by) = (best % SIZE, best / SIZE);
+        for y in 0..SIZE {
+            let dy = (y + SIZE - by) % SIZE;
+            for x in 0..SIZE {
+                let dx = (x + SIZE - bx) % SIZE;
+                energy[y * SIZE + x] += kernel[dy * SIZE + dx];
+            }
+        }
+    }
+    rank
+}
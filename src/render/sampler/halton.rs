@@ -0,0 +1,74 @@
+use super::*;
+
+// Bases de la suite de Halton, une par dimension
+const PRIMES: [u32; 32] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131,
+];
+
+// Suite de Halton : la dimension d utilise l'inverse radical en base du d-ième nombre premier.
+// Les chiffres sont permutés (une permutation par pixel, dimension et position) pour casser les
+// alignements entre dimensions de grandes bases et pour que les pixels voisins ne partagent pas
+// les mêmes motifs
+#[derive(Debug, Clone)]
+pub struct HaltonSampler {
+    seed: u64,
+    pixel: u64,
+    index: u64,
+    dimension: usize,
+}
+
+impl HaltonSampler {
+    pub fn new(seed: u64) -> Self {
+        HaltonSampler {
+            seed,
+            pixel: 0,
+            index: 0,
+            dimension: 0,
+        }
+    }
+}
+
+impl Sampler for HaltonSampler {
+    fn start_sample(&mut self, x: usize, y: usize, index: usize) {
+        self.pixel = hash(&[x as u64, y as u64]);
+        self.index = index as u64;
+        self.dimension = 0;
+    }
+
+    fn next_1d(&mut self) -> f64 {
+        let dimension = self.dimension;
+        self.dimension += 1;
+        // Au-delà des bases disponibles, on revient à des valeurs indépendantes
+        match PRIMES.get(dimension) {
+            Some(&base) => {
+                let key = hash(&[self.seed, self.pixel, dimension as u64]);
+                scrambled_radical_inverse(self.index, base, key)
+            }
+            None => to_unit(hash(&[self.seed, self.pixel, self.index, dimension as u64])),
+        }
+    }
+}
+
+// Chiffres de `index` en base `base` lus en miroir après la virgule, chacun passé par une
+// permutation qui dépend de sa position. Les zéros de tête permutés donneraient des chiffres
+// quelconques : ils sont remplacés par une valeur aléatoire fixe, tirée une fois pour toutes
+pub fn scrambled_radical_inverse(mut index: u64, base: u32, key: u64) -> f64 {
+    let inverse_base = 1.0 / base as f64;
+    let mut factor = inverse_base;
+    let mut value = 0.0;
+    let mut position = 0;
+    while factor > f64::EPSILON {
+        if index == 0 {
+            value += factor * base as f64 * to_unit(hash(&[key, position, 1]));
+            break;
+        }
+        let digit = (index % base as u64) as u32;
+        let digit_key = hash(&[key, position]) as u32;
+        value += permute(digit, base, digit_key) as f64 * factor;
+        index /= base as u64;
+        factor *= inverse_base;
+        position += 1;
+    }
+    value.min(1.0 - f64::EPSILON)
+}
@@ -0,0 +1,148 @@
+pub mod blue_noise;
+pub mod halton;
+pub mod sobol;
+pub mod stratified;
+
+use blue_noise::*;
+use halton::*;
+use sobol::*;
+use stratified::*;
+
+// Nombres pseudo-aléatoires du rendu. Chaque valeur ne dépend que de la graine, du pixel, du numéro
+// d'échantillon et de la dimension : deux rendus de la même scène sont identiques au bit près,
+// quel que soit l'ordre dans lequel les pixels sont calculés
+
+pub trait Sampler {
+    // Prépare l'échantillon `index` du pixel (x, y) ; les dimensions repartent de zéro
+    fn start_sample(&mut self, x: usize, y: usize, index: usize);
+
+    // Dimension suivante de l'échantillon courant, dans [0, 1[
+    fn next_1d(&mut self) -> f64;
+
+    // Deux dimensions suivantes ; les échantillonneurs stratifiés les répartissent ensemble dans le carré
+    fn next_2d(&mut self) -> (f64, f64) {
+        let u = self.next_1d();
+        let v = self.next_1d();
+        (u, v)
+    }
+}
+
+// Échantillonneur choisi pour un rendu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SamplerKind {
+    Independent, // Aléatoire pur
+    Stratified,  // Une strate (jittered) par échantillon du pixel
+    Halton,      // Suite de Halton aux chiffres permutés par pixel et par position
+    Sobol,       // Sobol brouillé par permutation d'Owen
+    BlueNoise,   // Erreur répartie en bruit bleu d'un pixel à l'autre
+}
+
+impl SamplerKind {
+    // `samples` : nombre d'échantillons prévus par pixel (utilisé par la stratification)
+    pub fn build(&self, seed: u64, samples: usize) -> Box<dyn Sampler> {
+        match self {
+            SamplerKind::Independent => Box::new(IndependentSampler::new(seed)),
+            SamplerKind::Stratified => Box::new(StratifiedSampler::new(seed, samples)),
+            SamplerKind::Halton => Box::new(HaltonSampler::new(seed)),
+            SamplerKind::Sobol => Box::new(SobolSampler::new(seed)),
+            SamplerKind::BlueNoise => Box::new(BlueNoiseSampler::new(seed)),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "independent" | "random" => Some(SamplerKind::Independent),
+            "stratified" => Some(SamplerKind::Stratified),
+            "halton" => Some(SamplerKind::Halton),
+            "sobol" => Some(SamplerKind::Sobol),
+            "bluenoise" | "blue-noise" => Some(SamplerKind::BlueNoise),
+            _ => None,
+        }
+    }
+}
+
+// Échantillons indépendants, obtenus en hachant (graine, pixel, échantillon, dimension)
+#[derive(Debug, Clone)]
+pub struct IndependentSampler {
+    seed: u64,
+    pixel: u64,
+    index: u64,
+    dimension: u64,
+}
+
+impl IndependentSampler {
+    pub fn new(seed: u64) -> Self {
+        IndependentSampler {
+            seed,
+            pixel: 0,
+            index: 0,
+            dimension: 0,
+        }
+    }
+}
+
+impl Sampler for IndependentSampler {
+    fn start_sample(&mut self, x: usize, y: usize, index: usize) {
+        self.pixel = hash(&[x as u64, y as u64]);
+        self.index = index as u64;
+        self.dimension = 0;
+    }
+
+    fn next_1d(&mut self) -> f64 {
+        let value = hash(&[self.seed, self.pixel, self.index, self.dimension]);
+        self.dimension += 1;
+        to_unit(value)
+    }
+}
+
+// Mélange de splitmix64, appliqué à chaque valeur combinée
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+pub fn hash(values: &[u64]) -> u64 {
+    values.iter().fold(0x9e3779b97f4a7c15, |state, value| {
+        mix(state ^ mix(value.wrapping_add(0x9e3779b97f4a7c15)))
+    })
+}
+
+// 53 bits de poids fort -> [0, 1[
+pub fn to_unit(value: u64) -> f64 {
+    (value >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+// Permutation pseudo-aléatoire de [0, length[ sans table (Kensler, « Correlated Multi-Jittered Sampling »)
+pub fn permute(mut index: u32, length: u32, key: u32) -> u32 {
+    let mut mask = length.wrapping_sub(1);
+    mask |= mask >> 1;
+    mask |= mask >> 2;
+    mask |= mask >> 4;
+    mask |= mask >> 8;
+    mask |= mask >> 16;
+    loop {
+        index ^= key;
+        index = index.wrapping_mul(0xe170893d);
+        index ^= key >> 16;
+        index ^= (index & mask) >> 4;
+        index ^= key >> 8;
+        index = index.wrapping_mul(0x0929eb3f);
+        index ^= key >> 23;
+        index ^= (index & mask) >> 1;
+        index = index.wrapping_mul(1 | key >> 27);
+        index = index.wrapping_mul(0x6935fa69);
+        index ^= (index & mask) >> 11;
+        index = index.wrapping_mul(0x74dcb303);
+        index ^= (index & mask) >> 2;
+        index = index.wrapping_mul(0x9e501cc3);
+        index ^= (index & mask) >> 2;
+        index = index.wrapping_mul(0xc860a3df);
+        index &= mask;
+        index ^= index >> 5;
+        if index < length {
+            break;
+        }
+    }
+    (index.wrapping_add(key)) % length
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+// Sobol brouillé par permutation d'Owen à base de hachage (Burley, « Practical Hash-based Owen
+// Scrambling », 2020). Chaque paire de dimensions reprend les deux premières dimensions de Sobol
+// avec son propre brouillage et son propre ordre d'échantillons (« padding »), ce qui évite les
+// corrélations des dimensions élevées de Sobol
+#[derive(Debug, Clone)]
+pub struct SobolSampler {
+    seed: u64,
+    pixel: u64,
+    index: u32,
+    dimension: u64,
+}
+
+impl SobolSampler {
+    pub fn new(seed: u64) -> Self {
+        SobolSampler {
+            seed,
+            pixel: 0,
+            index: 0,
+            dimension: 0,
+        }
+    }
+
+    fn scramble_seed(&self, axis: u64) -> u32 {
+        hash(&[self.seed, self.pixel, self.dimension, axis]) as u32
+    }
+}
+
+impl Sampler for SobolSampler {
+    fn start_sample(&mut self, x: usize, y: usize, index: usize) {
+        self.pixel = hash(&[x as u64, y as u64]);
+        self.index = index as u32;
+        self.dimension = 0;
+    }
+
+    fn next_1d(&mut self) -> f64 {
+        let index = nested_uniform_scramble(self.index, self.scramble_seed(0));
+        let value = nested_uniform_scramble(sobol(index, 0), self.scramble_seed(1));
+        self.dimension += 1;
+        to_unit_u32(value)
+    }
+
+    fn next_2d(&mut self) -> (f64, f64) {
+        let index = nested_uniform_scramble(self.index, self.scramble_seed(0));
+        let u = nested_uniform_scramble(sobol(index, 0), self.scramble_seed(1));
+        let v = nested_uniform_scramble(sobol(index, 1), self.scramble_seed(2));
+        self.dimension += 2;
+        (to_unit_u32(u), to_unit_u32(v))
+    }
+}
+
+// Deux premières dimensions de Sobol : van der Corput puis le polynôme x + 1
+pub fn sobol(mut index: u32, dimension: u32) -> u32 {
+    if dimension == 0 {
+        return index.reverse_bits();
+    }
+    let mut result = 0;
+    let mut direction = 1u32 << 31;
+    while index != 0 {
+        if index & 1 != 0 {
+            result ^= direction;
+        }
+        direction ^= direction >> 1;
+        index >>= 1;
+    }
+    result
+}
+
+// Permutation de Laine-Karras : chaque bit n'est modifié qu'en fonction des bits de poids plus faible
+fn laine_karras_permutation(mut x: u32, seed: u32) -> u32 {
+    x = x.wrapping_add(seed);
+    x ^= x.wrapping_mul(0x6c50b47c);
+    x ^= x.wrapping_mul(0xb82f1e52);
+    x ^= x.wrapping_mul(0xc7afe638);
+    x ^= x.wrapping_mul(0x8d22f6e6);
+    x
+}
+
+// Brouillage d'Owen : la même permutation appliquée aux bits inversés
+pub fn nested_uniform_scramble(x: u32, seed: u32) -> u32 {
+    laine_karras_permutation(x.reverse_bits(), seed).reverse_bits()
+}
+
+fn to_unit_u32(value: u32) -> f64 {
+    value as f64 / 4294967296.0
+}
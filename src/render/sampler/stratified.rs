@@ -0,0 +1,64 @@
+use super::*;
+
+// Échantillonnage stratifié : chaque dimension (ou paire de dimensions) est découpée en autant de
+// strates que d'échantillons prévus par pixel, et chaque échantillon tombe au hasard dans la sienne.
+// L'attribution des strates est permutée par pixel et par dimension pour décorréler les dimensions
+#[derive(Debug, Clone)]
+pub struct StratifiedSampler {
+    seed: u64,
+    samples: usize,
+    pixel: u64,
+    index: usize,
+    dimension: u64,
+}
+
+impl StratifiedSampler {
+    pub fn new(seed: u64, samples: usize) -> Self {
+        StratifiedSampler {
+            seed,
+            samples: samples.max(1),
+            pixel: 0,
+            index: 0,
+            dimension: 0,
+        }
+    }
+
+    // Strate de l'échantillon courant parmi `count` ; au-delà de `count` échantillons, une nouvelle
+    // série de strates recommence avec une autre permutation
+    fn stratum(&self, count: usize) -> usize {
+        let round = (self.index / count) as u64;
+        let key = hash(&[self.seed, self.pixel, self.dimension, round]) as u32;
+        permute((self.index % count) as u32, count as u32, key) as usize
+    }
+
+    fn jitter(&self, axis: u64) -> f64 {
+        to_unit(hash(&[self.seed, self.pixel, self.index as u64, self.dimension, axis]))
+    }
+}
+
+impl Sampler for StratifiedSampler {
+    fn start_sample(&mut self, x: usize, y: usize, index: usize) {
+        self.pixel = hash(&[x as u64, y as u64]);
+        self.index = index;
+        self.dimension = 0;
+    }
+
+    fn next_1d(&mut self) -> f64 {
+        let count = self.samples;
+        let value = (self.stratum(count) as f64 + self.jitter(0)) / count as f64;
+        self.dimension += 1;
+        value
+    }
+
+    // Grille de colonnes × lignes couvrant au moins le nombre d'échantillons ; les cases restantes
+    // sont tirées au sort par la permutation, ce qui garde l'estimation sans biais
+    fn next_2d(&mut self) -> (f64, f64) {
+        let columns = (self.samples as f64).sqrt().ceil() as usize;
+        let rows = self.samples.div_ceil(columns);
+        let cell = self.stratum(columns * rows);
+        let u = ((cell % columns) as f64 + self.jitter(0)) / columns as f64;
+        let v = ((cell / columns) as f64 + self.jitter(1)) / rows as f64;
+        self.dimension += 2;
+        (u, v)
+    }
+}
@@ -0,0 +1,168 @@
+use crate::config::aabb::*;
+use crate::config::animation::*;
+use crate::config::hittable::*;
+use crate::config::ray::*;
+use crate::config::validation::*;
+use crate::config::vec3::*;
+
+// Nombre d'instants examinés pour englober la trajectoire d'un objet animé
+const MOTION_STEPS: usize = 16;
+
+// Objet animé : translation, rotation autour de Y (autour d'un pivot) et couleur/émission variables dans le temps.
+// L'objet est intersecté à l'instant porté par chaque rayon, ce qui donne le flou de mouvement
+pub struct Animated {
+    pub object: Box<dyn Hittable>,
+    pub pivot: Vec3,
+    pub translation: Track<Vec3>,
+    pub velocity: Vec3, // Mouvement linéaire (unités par seconde), ajouté à la translation
+    pub rotation_y: Track<f64>, // Degrés
+    pub color: Option<Track<Vec3>>,
+    pub emission: Option<Track<Vec3>>,
+    time: f64,
+}
+
+impl Animated {
+    pub fn new(object: Box<dyn Hittable>) -> Self {
+        Animated {
+            object,
+            pivot: Vec3::new(0.0, 0.0, 0.0),
+            translation: Track::constant(Vec3::new(0.0, 0.0, 0.0)),
+            velocity: Vec3::new(0.0, 0.0, 0.0),
+            rotation_y: Track::constant(0.0),
+            color: None,
+            emission: None,
+            time: 0.0,
+        }
+    }
+
+    pub fn with_translation(mut self, translation: Track<Vec3>) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    pub fn with_velocity(mut self, velocity: Vec3) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    pub fn with_rotation_y(mut self, pivot: Vec3, rotation_y: Track<f64>) -> Self {
+        self.pivot = pivot;
+        self.rotation_y = rotation_y;
+        self
+    }
+
+    pub fn with_color(mut self, color: Track<Vec3>) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_emission(mut self, emission: Track<Vec3>) -> Self {
+        self.emission = Some(emission);
+        self
+    }
+
+    // Déplacement et angle (radians) à l'instant donné
+    fn transform(&self, time: f64) -> (Vec3, f64) {
+        let offset = self.translation.evaluate(time).unwrap_or(Vec3::new(0.0, 0.0, 0.0))
+            + self.velocity * time;
+        let angle = self.rotation_y.evaluate(time).unwrap_or(0.0).to_radians();
+        (offset, angle)
+    }
+
+    // Repère de l'objet -> monde
+    fn to_world(&self, point: &Vec3, offset: &Vec3, angle: f64) -> Vec3 {
+        (*point - self.pivot).rotate_y(angle) + self.pivot + *offset
+    }
+}
+
+impl Hittable for Animated {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let (offset, angle) = self.transform(ray.time);
+
+        // Le rayon est ramené dans le repère de l'objet ; la transformation est rigide donc t est conservé
+        let local_ray = Ray {
+            origin: (ray.origin - offset - self.pivot).rotate_y(-angle) + self.pivot,
+            direction: ray.direction.rotate_y(-angle),
+            time: ray.time,
+        };
+        let mut hit = self.object.hit(&local_ray, t_min, t_max)?;
+
+        hit.point = self.to_world(&hit.point, &offset, angle);
+        hit.normal = hit.normal.rotate_y(angle);
+        if let Some(color) = self.color.as_ref().and_then(|c| c.evaluate(ray.time)) {
+            hit.color = color;
+            hit.material.color = color;
+        }
+        if let Some(emission) = self.emission.as_ref().and_then(|e| e.evaluate(ray.time)) {
+            hit.material.emission = emission;
+        }
+        Some(hit)
+    }
+
+    fn validate(&self) -> Result<(), GeometryError> {
+        self.object.validate()
+    }
+
+    // Position de l'objet à l'instant courant (set_time)
+    fn contains(&self, point: &Vec3) -> bool {
+        let (offset, angle) = self.transform(self.time);
+        let local = (*point - offset - self.pivot).rotate_y(-angle) + self.pivot;
+        self.object.contains(&local)
+    }
+
+    fn emission(&self) -> Vec3 {
+        match self.emission.as_ref().and_then(|e| e.evaluate(self.time)) {
+            Some(emission) => emission,
+            None => self.object.emission(),
+        }
+    }
+
+    fn area(&self) -> f64 {
+        self.object.area()
+    }
+
+    // Boîte de l'objet aux instants de l'intervalle et aux clés qu'il contient, agrandie du plus grand
+    // déplacement entre deux instants voisins pour couvrir le chemin parcouru entre eux
+    fn bounding_box(&self, time0: f64, time1: f64) -> Option<Aabb> {
+        let local = self.object.bounding_box(time0, time1)?;
+        let mut times = (0..=MOTION_STEPS)
+            .map(|step| time0 + (time1 - time0) * step as f64 / MOTION_STEPS as f64)
+            .chain(self.translation.keys.iter().map(|key| key.time))
+            .chain(self.rotation_y.keys.iter().map(|key| key.time))
+            .filter(|time| *time >= time0 && *time <= time1)
+            .collect::<Vec<_>>();
+        times.sort_by(f64::total_cmp);
+
+        let mut bounds: Option<Aabb> = None;
+        let mut previous: Option<[Vec3; 8]> = None;
+        let mut margin: f64 = 0.0;
+        for time in times {
+            let (offset, angle) = self.transform(time);
+            let corners = local.corners().map(|corner| self.to_world(&corner, &offset, angle));
+            if let Some(previous) = previous {
+                for (a, b) in previous.iter().zip(corners.iter()) {
+                    margin = margin.max((*a - *b).length());
+                }
+            }
+            let aabb = Aabb::from_points(&corners)?;
+            bounds = Some(bounds.map_or(aabb, |bounds| bounds.union(&aabb)));
+            previous = Some(corners);
+        }
+        bounds.map(|bounds| bounds.expand(margin))
+    }
+
+    // Les émetteurs sont échantillonnés à l'instant de l'image (set_time), pas à celui de chaque rayon
+    fn sample_surface(&self, u: f64, v: f64) -> Option<SurfaceSample> {
+        let (offset, angle) = self.transform(self.time);
+        let sample = self.object.sample_surface(u, v)?;
+        Some(SurfaceSample {
+            point: self.to_world(&sample.point, &offset, angle),
+            normal: sample.normal.rotate_y(angle),
+        })
+    }
+
+    fn set_time(&mut self, time: f64) {
+        self.time = time;
+        self.object.set_time(time);
+    }
+}
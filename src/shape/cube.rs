@@ -1,21 +1,40 @@
 use crate::config::hittable::*;
+use crate::config::aabb::*;
 use crate::config::ray::*;
+use crate::config::material::*;
+use crate::config::validation::*;
 use crate::config::vec3::*;
 
 pub struct Cube {
     min: Vec3,
     max: Vec3,
-    color: Vec3,
+    material: Material,
 }
 
 impl Cube {
     pub fn new(min: Vec3, max: Vec3, color: Vec3) -> Self {
-        Cube { min, max, color }
+        Cube {
+            min,
+            max,
+            material: Material::new(color),
+        }
+    }
+
+    // Comme new, en refusant des coins non finis ou une boîte vide
+    pub fn try_new(min: Vec3, max: Vec3, color: Vec3) -> Result<Self, GeometryError> {
+        let cube = Cube::new(min, max, color);
+        cube.validate()?;
+        Ok(cube)
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
     }
 
     pub fn translate(&mut self, offset: Vec3) {
-        self.min = self.min.add(&offset);
-        self.max = self.max.add(&offset);
+        self.min += offset;
+        self.max += offset;
     }
 
     pub fn rotate_y(&mut self, angle: f64) {
@@ -57,78 +76,136 @@ impl Cube {
 
 impl Hittable for Cube {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
-        let mut tmin = (self.min.x - ray.origin.x) / ray.direction.x;
-        let mut tmax = (self.max.x - ray.origin.x) / ray.direction.x;
-
-        if tmin > tmax {
-            std::mem::swap(&mut tmin, &mut tmax);
+        // Intersection des intervalles de t des trois tranches
+        let mut tmin = f64::NEG_INFINITY;
+        let mut tmax = f64::INFINITY;
+        for (min, max, origin, direction) in [
+            (self.min.x, self.max.x, ray.origin.x, ray.direction.x),
+            (self.min.y, self.max.y, ray.origin.y, ray.direction.y),
+            (self.min.z, self.max.z, ray.origin.z, ray.direction.z),
+        ] {
+            let (t0, t1) = slab(min, max, origin, direction)?;
+            tmin = tmin.max(t0);
+            tmax = tmax.min(t1);
+            if tmin > tmax {
+                return None;
+            }
         }
 
-        let mut tymin = (self.min.y - ray.origin.y) / ray.direction.y;
-        let mut tymax = (self.max.y - ray.origin.y) / ray.direction.y;
+        // Depuis l'intérieur du cube, l'entrée est derrière l'origine : c'est la sortie qui compte
+        let t = if tmin > t_min { tmin } else { tmax };
+        if t > t_min && t < t_max {
+            let point = ray.point_at_parameter(t);
 
-        if tymin > tymax {
-            std::mem::swap(&mut tymin, &mut tymax);
-        }
+            // Normale de la face la plus proche du point touché
+            let faces = [
+                ((point.x - self.min.x).abs(), Vec3::new(-1.0, 0.0, 0.0)),
+                ((point.x - self.max.x).abs(), Vec3::new(1.0, 0.0, 0.0)),
+                ((point.y - self.min.y).abs(), Vec3::new(0.0, -1.0, 0.0)),
+                ((point.y - self.max.y).abs(), Vec3::new(0.0, 1.0, 0.0)),
+                ((point.z - self.min.z).abs(), Vec3::new(0.0, 0.0, -1.0)),
+                ((point.z - self.max.z).abs(), Vec3::new(0.0, 0.0, 1.0)),
+            ];
+            let (_, normal) = faces
+                .iter()
+                .fold(faces[0], |closest, face| if face.0 < closest.0 { *face } else { closest });
 
-        if tmin > tymax || tymin > tmax {
-            return None;
+            return Some(HitRecord {
+                t,
+                point,
+                normal,
+                color: self.material.color,
+                material: self.material,
+            });
         }
 
-        if tymin > tmin {
-            tmin = tymin;
-        }
+        None
+    }
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<Aabb> {
+        Some(Aabb::new(self.min, self.max))
+    }
 
-        if tymax < tmax {
-            tmax = tymax;
+    fn validate(&self) -> Result<(), GeometryError> {
+        check_finite(&self.min, "min")?;
+        check_finite(&self.max, "max")?;
+        if self.min.x < self.max.x && self.min.y < self.max.y && self.min.z < self.max.z {
+            Ok(())
+        } else {
+            Err(GeometryError::EmptyBox)
         }
+    }
 
-        let mut tzmin = (self.min.z - ray.origin.z) / ray.direction.z;
-        let mut tzmax = (self.max.z - ray.origin.z) / ray.direction.z;
-
-        if tzmin > tzmax {
-            std::mem::swap(&mut tzmin, &mut tzmax);
-        }
+    fn contains(&self, point: &Vec3) -> bool {
+        (self.min.x < point.x && point.x < self.max.x)
+            && (self.min.y < point.y && point.y < self.max.y)
+            && (self.min.z < point.z && point.z < self.max.z)
+    }
 
-        if tmin > tzmax || tzmin > tmax {
-            return None;
-        }
+    fn emission(&self) -> Vec3 {
+        self.material.emission
+    }
 
-        if tzmin > tmin {
-            tmin = tzmin;
-        }
+    fn area(&self) -> f64 {
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
 
-        if tzmax < tmax {
-            tmax = tzmax;
+    // Choix d'une face proportionnellement à son aire, puis d'un point uniforme sur celle-ci
+    fn sample_surface(&self, u: f64, v: f64) -> Option<SurfaceSample> {
+        let size = self.max - self.min;
+        let faces = [size.y * size.z, size.x * size.z, size.x * size.y];
+        let total = 2.0 * (faces[0] + faces[1] + faces[2]);
+        if total <= 0.0 {
+            return None;
         }
 
-        if tmin < t_max && tmax > t_min {
-            let t = tmin;
-            let point = ray.point_at_parameter(t);
-
-            // Calcul de la normale
-            let normal = if (point.x - self.min.x).abs() < 0.0001 {
-                Vec3::new(-1.0, 0.0, 0.0)
-            } else if (point.x - self.max.x).abs() < 0.0001 {
-                Vec3::new(1.0, 0.0, 0.0)
-            } else if (point.y - self.min.y).abs() < 0.0001 {
-                Vec3::new(0.0, -1.0, 0.0)
-            } else if (point.y - self.max.y).abs() < 0.0001 {
-                Vec3::new(0.0, 1.0, 0.0)
-            } else if (point.z - self.min.z).abs() < 0.0001 {
-                Vec3::new(0.0, 0.0, -1.0)
-            } else {
-                Vec3::new(0.0, 0.0, 1.0)
-            };
-
-            return Some(HitRecord {
-                t,
-                point,
-                normal,
-                color: self.color,
-            });
+        // u choisit la face (6 faces, deux par axe) puis est réutilisé pour la position
+        let mut target = u * total;
+        let mut face = 5;
+        for index in 0..6 {
+            let face_area = faces[index / 2];
+            if target < face_area {
+                face = index;
+                break;
+            }
+            target -= face_area;
         }
+        let s = (target / faces[face / 2]).clamp(0.0, 1.0);
+        let (lo, hi) = (self.min, self.max);
+        let upper = face % 2 == 1;
+        let side = |low: f64, high: f64| if upper { high } else { low };
+        let sign = if upper { 1.0 } else { -1.0 };
+
+        let (point, normal) = match face / 2 {
+            0 => (
+                Vec3::new(side(lo.x, hi.x), lo.y + s * size.y, lo.z + v * size.z),
+                Vec3::new(sign, 0.0, 0.0),
+            ),
+            1 => (
+                Vec3::new(lo.x + s * size.x, side(lo.y, hi.y), lo.z + v * size.z),
+                Vec3::new(0.0, sign, 0.0),
+            ),
+            _ => (
+                Vec3::new(lo.x + s * size.x, lo.y + v * size.y, side(lo.z, hi.z)),
+                Vec3::new(0.0, 0.0, sign),
+            ),
+        };
+        Some(SurfaceSample { point, normal })
+    }
+}
 
-        None
+// Intervalle de t pendant lequel le rayon est dans la tranche [min, max] d'un axe. Un rayon
+// parallèle à la tranche y est tout entier ou jamais : on le traite à part plutôt que de calculer
+// 0 × ∞ (NaN) quand l'origine est sur un des plans
+fn slab(min: f64, max: f64, origin: f64, direction: f64) -> Option<(f64, f64)> {
+    if direction == 0.0 {
+        return if origin < min || origin > max {
+            None
+        } else {
+            Some((f64::NEG_INFINITY, f64::INFINITY))
+        };
     }
+    let t0 = (min - origin) / direction;
+    let t1 = (max - origin) / direction;
+    Some((t0.min(t1), t0.max(t1)))
 }
@@ -1,14 +1,19 @@
 
 use crate::config::vec3::*;
 use crate::config::hittable::*;
+use crate::config::aabb::*;
 use crate::config::ray::*;
+use crate::config::material::*;
+use crate::config::onb::*;
+use crate::config::validation::*;
+use std::f64::consts::PI;
 
 pub struct Cylinder {
     pub base: Vec3,       // Point de base du cylindre
     pub axis: Vec3,       // Axe du cylindre (vecteur direction)
     pub radius: f64,      // Rayon du cylindre
     pub height: f64,      // Hauteur du cylindre
-    pub color: Vec3,      // Couleur
+    pub material: Material, // Matériau (couleur, transmission...)
 }
 
 impl Cylinder {
@@ -18,55 +23,116 @@ impl Cylinder {
             axis: axis.normalize(),
             radius,
             height,
-            color,
+            material: Material::new(color),
         }
     }
+
+    // Comme new, en refusant un axe nul et un rayon ou une hauteur nuls, négatifs ou NaN
+    pub fn try_new(
+        base: Vec3,
+        axis: Vec3,
+        radius: f64,
+        height: f64,
+        color: Vec3,
+    ) -> Result<Self, GeometryError> {
+        check_finite(&base, "base")?;
+        let axis = check_axis(&axis, "axe")?;
+        check_positive(radius, "rayon")?;
+        check_positive(height, "hauteur")?;
+        Ok(Cylinder::new(base, axis, radius, height, color))
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
 }
 
 
 impl Hittable for Cylinder {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
-        let oc = ray.origin.sub(&self.base);
+        let oc = ray.origin - self.base;
         let axis_dot_dir = self.axis.dot(&ray.direction);
         let axis_dot_oc = self.axis.dot(&oc);
 
-        let a = ray.direction.sub(&self.axis.mul(axis_dot_dir)).length_squared();
-        let b = 2.0 * oc.sub(&self.axis.mul(axis_dot_oc)).dot(&ray.direction.sub(&self.axis.mul(axis_dot_dir)));
-        let c = oc.sub(&self.axis.mul(axis_dot_oc)).length_squared() - self.radius * self.radius;
+        // Composantes perpendiculaires à l'axe
+        let direction = ray.direction - self.axis * axis_dot_dir;
+        let offset = oc - self.axis * axis_dot_oc;
 
+        let a = direction.length_squared();
+        let b = 2.0 * offset.dot(&direction);
+        let c = offset.length_squared() - self.radius * self.radius;
+
+        // Rayon parallèle à l'axe : il ne coupe pas la surface latérale
+        if a < 1e-12 {
+            return None;
+        }
         let discriminant = b * b - 4.0 * a * c;
         if discriminant < 0.0 {
             return None; // Pas d'intersection
         }
 
+        // Première racine dans l'intervalle et dans les limites du cylindre fini ; la seconde sert
+        // quand la première est derrière l'origine (rayon parti de l'intérieur) ou hors de la hauteur
         let sqrt_d = discriminant.sqrt();
-        let mut t = (-b - sqrt_d) / (2.0 * a);
-
-        // Vérifiez si le point est dans les limites du cylindre fini
-        let point = ray.point_at_parameter(t);
-        let projection_length = self.axis.dot(&point.sub(&self.base));
-        if projection_length < 0.0 || projection_length > self.height {
-            t = (-b + sqrt_d) / (2.0 * a);
-            let point = ray.point_at_parameter(t);
-            let projection_length = self.axis.dot(&point.sub(&self.base));
-            if projection_length < 0.0 || projection_length > self.height {
-                return None;
-            }
-        }
-
-        if t < t_min || t > t_max {
-            return None;
-        }
+        let t = [(-b - sqrt_d) / (2.0 * a), (-b + sqrt_d) / (2.0 * a)]
+            .into_iter()
+            .find(|&t| {
+                let projection_length = self.axis.dot(&(ray.point_at_parameter(t) - self.base));
+                t > t_min && t < t_max && (0.0..=self.height).contains(&projection_length)
+            })?;
 
         let point = ray.point_at_parameter(t);
-        let projection = self.axis.mul(self.axis.dot(&point.sub(&self.base)));
-        let normal = point.sub(&self.base.add(&projection)).normalize();
+        let projection = self.axis * self.axis.dot(&(point - self.base));
+        let normal = (point - (self.base + projection)).normalize();
 
         Some(HitRecord {
             t,
             point,
             normal,
-            color: self.color,
+            color: self.material.color,
+            material: self.material,
         })
     }
-}
\ No newline at end of file
+    // Boîte des deux disques extrêmes : pour chaque axe, l'étendue d'un disque vaut r·√(1 - a²)
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<Aabb> {
+        let extent = |a: f64| self.radius * (1.0 - a * a).max(0.0).sqrt();
+        let extent = Vec3::new(extent(self.axis.x), extent(self.axis.y), extent(self.axis.z));
+        let top = self.base + self.axis * self.height;
+        Some(Aabb::new(self.base - extent, self.base + extent)
+            .union(&Aabb::new(top - extent, top + extent)))
+    }
+
+    fn validate(&self) -> Result<(), GeometryError> {
+        check_finite(&self.base, "base")?;
+        check_axis(&self.axis, "axe")?;
+        check_positive(self.radius, "rayon")?;
+        check_positive(self.height, "hauteur")
+    }
+
+    // Volume délimité par la surface latérale, entre la base et le sommet
+    fn contains(&self, point: &Vec3) -> bool {
+        let offset = *point - self.base;
+        let along = offset.dot(&self.axis);
+        along > 0.0
+            && along < self.height
+            && (offset - self.axis * along).length_squared() < self.radius * self.radius
+    }
+
+    fn emission(&self) -> Vec3 {
+        self.material.emission
+    }
+
+    // Seule la surface latérale est intersectée, c'est donc elle qui est échantillonnée
+    fn area(&self) -> f64 {
+        2.0 * PI * self.radius * self.height
+    }
+
+    fn sample_surface(&self, u: f64, v: f64) -> Option<SurfaceSample> {
+        let basis = Onb::from_w(&self.axis);
+        let phi = 2.0 * PI * v;
+        let normal = basis.to_world(&Vec3::new(phi.cos(), phi.sin(), 0.0));
+        let point = self.base + self.axis * (u * self.height) + normal * self.radius;
+        Some(SurfaceSample { point, normal })
+    }
+}
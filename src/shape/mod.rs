@@ -1,4 +1,6 @@
 pub mod sphere;
 pub mod plane;
 pub mod cylindre;
-pub mod cube;
\ No newline at end of file
+pub mod cube;
+pub mod triangle;
+pub mod animated;
\ No newline at end of file
@@ -2,11 +2,13 @@
 use crate::config::vec3::*;
 use crate::config::hittable::*;
 use crate::config::ray::*;
+use crate::config::material::*;
+use crate::config::validation::*;
 
 pub struct Plane {
     pub point: Vec3,
     pub normal: Vec3,
-    pub color: Vec3,
+    pub material: Material,
 }
 
 impl Plane {
@@ -14,9 +16,21 @@ impl Plane {
         Plane {
             point,
             normal: normal.normalize(),
-            color,
+            material: Material::new(color),
         }
     }
+
+    // Comme new, en refusant un point non fini ou une normale nulle
+    pub fn try_new(point: Vec3, normal: Vec3, color: Vec3) -> Result<Self, GeometryError> {
+        check_finite(&point, "point")?;
+        let normal = check_axis(&normal, "normale")?;
+        Ok(Plane::new(point, normal, color))
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
 }
 
 impl Hittable for Plane {
@@ -27,9 +41,9 @@ impl Hittable for Plane {
             return None;
         }
 
-        let t = self.point.sub(&ray.origin).dot(&self.normal) / denom;
+        let t = (self.point - ray.origin).dot(&self.normal) / denom;
         
-        if t < t_min || t > t_max {
+        if t <= t_min || t >= t_max {
             return None;
         }
 
@@ -39,7 +53,16 @@ impl Hittable for Plane {
             t,
             point,
             normal: self.normal,
-            color: self.color,
+            color: self.material.color,
+            material: self.material,
         })
     }
-}
\ No newline at end of file
+    fn validate(&self) -> Result<(), GeometryError> {
+        check_finite(&self.point, "point")?;
+        check_axis(&self.normal, "normale").map(|_| ())
+    }
+
+    fn emission(&self) -> Vec3 {
+        self.material.emission
+    }
+}
@@ -1,11 +1,15 @@
 
 use crate::config::vec3::*;
 use crate::config::hittable::*;
+use crate::config::aabb::*;
 use crate::config::ray::*;
+use crate::config::material::*;
+use crate::config::validation::*;
+use std::f64::consts::PI;
 pub struct Sphere {
     pub center: Vec3,
     pub radius: f64,
-    pub color: Vec3,
+    pub material: Material,
 }
 
 impl Sphere {
@@ -13,14 +17,26 @@ impl Sphere {
         Sphere {
             center,
             radius,
-            color,
+            material: Material::new(color),
         }
     }
+
+    // Comme new, en refusant un centre non fini ou un rayon nul, négatif ou NaN
+    pub fn try_new(center: Vec3, radius: f64, color: Vec3) -> Result<Self, GeometryError> {
+        let sphere = Sphere::new(center, radius, color);
+        sphere.validate()?;
+        Ok(sphere)
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
 }
 
 impl Hittable for Sphere {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
-        let oc = ray.origin.sub(&self.center);
+        let oc = ray.origin - self.center;
         let a = ray.direction.dot(&ray.direction);
         let b = oc.dot(&ray.direction);
         let c = oc.dot(&oc) - self.radius * self.radius;
@@ -30,26 +46,60 @@ impl Hittable for Sphere {
             let temp = (-b - (b * b - a * c).sqrt()) / a;
             if temp < t_max && temp > t_min {
                 let point = ray.point_at_parameter(temp);
-                let normal = point.sub(&self.center).mul(1.0 / self.radius);
+                let normal = (point - self.center) * (1.0 / self.radius);
                 return Some(HitRecord {
                     t: temp,
                     point,
                     normal,
-                    color: self.color,
+                    color: self.material.color,
+                    material: self.material,
                 });
             }
             let temp = (-b + (b * b - a * c).sqrt()) / a;
             if temp < t_max && temp > t_min {
                 let point = ray.point_at_parameter(temp);
-                let normal = point.sub(&self.center).mul(1.0 / self.radius);
+                let normal = (point - self.center) * (1.0 / self.radius);
                 return Some(HitRecord {
                     t: temp,
                     point,
                     normal,
-                    color: self.color,
+                    color: self.material.color,
+                    material: self.material,
                 });
             }
         }
         None
     }
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<Aabb> {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        Some(Aabb::new(self.center - radius, self.center + radius))
+    }
+
+    fn validate(&self) -> Result<(), GeometryError> {
+        check_finite(&self.center, "centre")?;
+        check_positive(self.radius, "rayon")
+    }
+
+    fn contains(&self, point: &Vec3) -> bool {
+        (*point - self.center).length_squared() < self.radius * self.radius
+    }
+
+    fn emission(&self) -> Vec3 {
+        self.material.emission
+    }
+
+    fn area(&self) -> f64 {
+        4.0 * PI * self.radius * self.radius
+    }
+
+    fn sample_surface(&self, u: f64, v: f64) -> Option<SurfaceSample> {
+        let z = 1.0 - 2.0 * u;
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * PI * v;
+        let normal = Vec3::new(r * phi.cos(), r * phi.sin(), z);
+        Some(SurfaceSample {
+            point: self.center + normal * self.radius,
+            normal,
+        })
+    }
 }
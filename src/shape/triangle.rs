@@ -0,0 +1,117 @@
+
+use crate::config::vec3::*;
+use crate::config::hittable::*;
+use crate::config::aabb::*;
+use crate::config::ray::*;
+use crate::config::material::*;
+use crate::config::validation::*;
+
+// Triangle d'un maillage (sommets dans le sens trigonométrique vus depuis la face avant)
+pub struct Triangle {
+    pub v0: Vec3,
+    pub v1: Vec3,
+    pub v2: Vec3,
+    pub material: Material,
+}
+
+impl Triangle {
+    pub fn new(v0: Vec3, v1: Vec3, v2: Vec3, color: Vec3) -> Self {
+        Triangle {
+            v0,
+            v1,
+            v2,
+            material: Material::new(color),
+        }
+    }
+
+    // Comme new, en refusant des sommets non finis ou alignés
+    pub fn try_new(v0: Vec3, v1: Vec3, v2: Vec3, color: Vec3) -> Result<Self, GeometryError> {
+        let triangle = Triangle::new(v0, v1, v2, color);
+        triangle.validate()?;
+        Ok(triangle)
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = material;
+        self
+    }
+
+    fn normal(&self) -> Vec3 {
+        (self.v1 - self.v0).cross(&(self.v2 - self.v0)).normalize()
+    }
+}
+
+impl Hittable for Triangle {
+    // Algorithme de Möller-Trumbore
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let edge1 = self.v1 - self.v0;
+        let edge2 = self.v2 - self.v0;
+        let p = ray.direction.cross(&edge2);
+        let det = edge1.dot(&p);
+        if det.abs() < 1e-12 {
+            return None; // Rayon parallèle au triangle
+        }
+
+        let inv_det = 1.0 / det;
+        let s = ray.origin - self.v0;
+        let u = s.dot(&p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&edge1);
+        let v = ray.direction.dot(&q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+
+        let t = edge2.dot(&q) * inv_det;
+        if t <= t_min || t >= t_max {
+            return None;
+        }
+
+        Some(HitRecord {
+            t,
+            point: ray.point_at_parameter(t),
+            normal: self.normal(),
+            color: self.material.color,
+            material: self.material,
+        })
+    }
+
+    fn bounding_box(&self, _time0: f64, _time1: f64) -> Option<Aabb> {
+        // Légère marge pour qu'un triangle aligné sur un axe n'ait pas une boîte plate
+        Aabb::from_points(&[self.v0, self.v1, self.v2]).map(|aabb| aabb.expand(1e-6))
+    }
+
+    fn validate(&self) -> Result<(), GeometryError> {
+        check_finite(&self.v0, "v0")?;
+        check_finite(&self.v1, "v1")?;
+        check_finite(&self.v2, "v2")?;
+        // Aire (au double près) nulle : la normale n'est pas définie
+        let cross = (self.v1 - self.v0).cross(&(self.v2 - self.v0));
+        if cross.length() < 1e-12 {
+            return Err(GeometryError::DegenerateTriangle);
+        }
+        Ok(())
+    }
+
+    fn emission(&self) -> Vec3 {
+        self.material.emission
+    }
+
+    fn area(&self) -> f64 {
+        0.5 * (self.v1 - self.v0).cross(&(self.v2 - self.v0)).length()
+    }
+
+    fn sample_surface(&self, u: f64, v: f64) -> Option<SurfaceSample> {
+        // Coordonnées barycentriques uniformes
+        let su = u.sqrt();
+        let b0 = 1.0 - su;
+        let b1 = v * su;
+        let point = self.v0 * b0 + self.v1 * b1 + self.v2 * (1.0 - b0 - b1);
+        Some(SurfaceSample {
+            point,
+            normal: self.normal(),
+        })
+    }
+}
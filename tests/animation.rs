@@ -0,0 +1,35 @@
+// Timeline : l'instant d'une image ne dépend que de son numéro, pas de la plage rendue, pour
+// qu'une séquence découpée en plusieurs rendus donne la même animation
+use rt::animation::*;
+
+#[test]
+fn first_frame_is_at_time_zero() {
+    let timeline = Timeline::new(24.0, 1, 10);
+    assert_eq!(timeline.time(1), 0.0);
+    assert_eq!(timeline.time(25), 1.0);
+}
+
+#[test]
+fn frame_time_does_not_depend_on_start_frame() {
+    let whole = Timeline::new(25.0, 1, 100);
+    for start in [1, 2, 11, 50, 99] {
+        let part = Timeline::new(25.0, start, 100);
+        for frame in part.frames() {
+            assert_eq!(
+                part.time(frame),
+                whole.time(frame),
+                "image {} (début {})",
+                frame,
+                start
+            );
+        }
+    }
+}
+
+#[test]
+fn shutter_opens_at_frame_time() {
+    let timeline = Timeline::new(24.0, 50, 59).with_shutter_angle(180.0);
+    let shutter = timeline.shutter(50);
+    assert_eq!(shutter.open, 49.0 / 24.0);
+    assert!((shutter.close - shutter.open - 0.5 / 24.0).abs() < 1e-12);
+}
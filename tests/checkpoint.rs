@@ -0,0 +1,96 @@
+// Points de reprise : un fichier enregistré se relit, un fichier corrompu est refusé avec une
+// erreur InvalidData au lieu d'allouer une taille lue sans contrôle
+use rt::filter::*;
+use rt::renderer::*;
+use rt::sampler::*;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+fn settings() -> RenderSettings {
+    RenderSettings {
+        width: 8,
+        height: 6,
+        samples: 1,
+        seed: 3,
+        sampler: SamplerKind::Sobol,
+        adaptive: None,
+        filter: Filter::default(),
+        aovs: false,
+        transparent: false,
+        region: None,
+        overscan: 0,
+    }
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("checkpoint");
+    fs::create_dir_all(&directory).unwrap();
+    directory.join(name)
+}
+
+// Fichier de point de reprise dont les octets `offset..offset + 8` sont remplacés par `value`
+fn corrupted(
+    name: &str,
+    settings: &RenderSettings,
+    offset: impl Fn(usize) -> usize,
+    value: u64,
+) -> PathBuf {
+    let path = scratch(name);
+    let mut state = RenderState::new(settings);
+    state.passes = 2;
+    state.save_checkpoint(&path, settings).unwrap();
+    let mut bytes = fs::read(&path).unwrap();
+    let at = offset(bytes.len());
+    bytes[at..at + 8].copy_from_slice(&value.to_le_bytes());
+    fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn checkpoint_round_trip() {
+    let settings = settings();
+    let path = scratch("round_trip.ckpt");
+    let mut state = RenderState::new(&settings);
+    state.passes = 5;
+    state.save_checkpoint(&path, &settings).unwrap();
+    let loaded = RenderState::load_checkpoint(&path, &settings).unwrap();
+    assert_eq!(loaded.passes, 5);
+    assert!(loaded.aovs.is_none());
+}
+
+#[test]
+fn huge_fingerprint_length_is_rejected() {
+    let settings = settings();
+    // La longueur de l'empreinte suit la signature de 8 octets
+    let path = corrupted("fingerprint.ckpt", &settings, |_| 8, u64::MAX / 2);
+    let error = RenderState::load_checkpoint(&path, &settings)
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn huge_light_count_is_rejected() {
+    let settings = settings();
+    // Sans AOV, le nombre de lumières est le dernier u64 du fichier
+    let path = corrupted("lights.ckpt", &settings, |length| length - 8, u64::MAX / 2);
+    let error = RenderState::load_checkpoint(&path, &settings)
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn other_settings_are_rejected() {
+    let path = scratch("settings.ckpt");
+    RenderState::new(&settings())
+        .save_checkpoint(&path, &settings())
+        .unwrap();
+    let other = RenderSettings {
+        seed: 4,
+        ..settings()
+    };
+    let error = RenderState::load_checkpoint(&path, &other).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
@@ -0,0 +1,257 @@
+// Images de référence : chaque scène est rendue en petit avec une graine fixe et comparée à
+// tests/golden/<nom>.png. En cas d'écart, l'image obtenue et une image des différences sont écrites
+// dans le dossier temporaire de cargo (chemins affichés dans l'erreur).
+// Après un changement volontaire du rendu : RT_BLESS=1 cargo test --test golden
+use indicatif::ProgressBar;
+use rt::ambient::*;
+use rt::animated::*;
+use rt::background::*;
+use rt::camera::*;
+use rt::cube::*;
+use rt::cylindre::*;
+use rt::image::*;
+use rt::light::*;
+use rt::material::*;
+use rt::plane::*;
+use rt::renderer::*;
+use rt::sampler::*;
+use rt::scene::*;
+use rt::sky::*;
+use rt::sphere::*;
+use rt::triangle::*;
+use rt::vec3::*;
+use rt::world::*;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 48;
+
+// Écart quadratique moyen toléré, en niveaux de gris sur 255 (arrondis flottants d'une plateforme à l'autre)
+const MAX_RMSE: f64 = 1.5;
+// Part des pixels qui peuvent s'écarter nettement : un défaut local ne se noie pas dans la moyenne
+const MAX_PIXEL_DIFFERENCE: u8 = 24;
+const MAX_DIFFERENT_PIXELS: f64 = 0.005;
+
+fn settings(samples: usize) -> RenderSettings {
+    RenderSettings {
+        width: WIDTH,
+        height: HEIGHT,
+        samples,
+        seed: 7,
+        sampler: SamplerKind::Sobol,
+        adaptive: None,
+        filter: rt::filter::Filter::default(),
+        aovs: false,
+        transparent: false,
+        region: None,
+        overscan: 0,
+    }
+}
+
+fn camera_settings(lookfrom: Vec3, lookat: Vec3) -> CameraSettings {
+    CameraSettings::new(
+        lookfrom,
+        lookat,
+        Vec3::new(0.0, 1.0, 0.0),
+        WIDTH as f64 / HEIGHT as f64,
+        Projection::Perspective { vfov: 50.0 },
+    )
+}
+
+fn floor() -> Plane {
+    Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Vec3::new(0.8, 0.8, 0.8),
+    )
+}
+
+fn render_image(scene: &Scene, camera: &CameraSettings, settings: &RenderSettings) -> Image {
+    let camera = camera.build(&scene.world);
+    render(scene, camera.as_ref(), settings, &ProgressBar::hidden()).image
+}
+
+fn read_png(path: &Path) -> (usize, usize, Vec<u8>) {
+    let decoder = png::Decoder::new(File::open(path).expect("image de référence illisible"));
+    let mut reader = decoder.read_info().expect("PNG invalide");
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).expect("PNG invalide");
+    assert_eq!(info.color_type, png::ColorType::Rgb, "{} doit être en RGB", path.display());
+    buffer.truncate(info.buffer_size());
+    (info.width as usize, info.height as usize, buffer)
+}
+
+fn compare(name: &str, image: &Image) {
+    let reference = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.png", name));
+    if std::env::var_os("RT_BLESS").is_some() {
+        std::fs::create_dir_all(reference.parent().unwrap()).unwrap();
+        image.write_png(&reference).unwrap();
+        return;
+    }
+    assert!(
+        reference.exists(),
+        "{} manquante : RT_BLESS=1 cargo test --test golden pour la créer",
+        reference.display()
+    );
+
+    let (width, height, expected) = read_png(&reference);
+    assert_eq!((width, height), (image.width, image.height), "{} : taille différente", name);
+    let actual: Vec<u8> = image.pixels.iter().flat_map(to_rgb8).collect();
+
+    let mut squares = 0.0;
+    let mut different = 0;
+    let mut diff = Image::new(width, height);
+    for (pixel, (a, e)) in diff
+        .pixels
+        .iter_mut()
+        .zip(actual.chunks(3).zip(expected.chunks(3)))
+    {
+        let delta = [0, 1, 2].map(|c| a[c].abs_diff(e[c]));
+        squares += delta.iter().map(|&d| (d as f64).powi(2)).sum::<f64>();
+        if delta.iter().any(|&d| d > MAX_PIXEL_DIFFERENCE) {
+            different += 1;
+        }
+        // Différences amplifiées, en couleurs linéaires (l'écriture applique le gamma)
+        let scale = |d: u8| ((d as f64 * 4.0) / 255.0).min(1.0).powi(2);
+        *pixel = Vec3::new(scale(delta[0]), scale(delta[1]), scale(delta[2]));
+    }
+    let rmse = (squares / actual.len() as f64).sqrt();
+    let different = different as f64 / (width * height) as f64;
+
+    if rmse > MAX_RMSE || different > MAX_DIFFERENT_PIXELS {
+        let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("golden");
+        std::fs::create_dir_all(&output).unwrap();
+        let (actual_path, diff_path) = (
+            output.join(format!("{}.actual.png", name)),
+            output.join(format!("{}.diff.png", name)),
+        );
+        image.write_png(&actual_path).unwrap();
+        diff.write_png(&diff_path).unwrap();
+        panic!(
+            "{} : écart {:.2} (max {}), {:.2} % de pixels différents (max {:.2} %)\n  obtenu : {}\n  différences : {}",
+            name,
+            rmse,
+            MAX_RMSE,
+            different * 100.0,
+            MAX_DIFFERENT_PIXELS * 100.0,
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
+
+// Primitives de base, ombres portées et spéculaire de Phong
+#[test]
+fn shapes() {
+    let mut world = World::new();
+    world.add(Box::new(floor()));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, 0.2, -1.0),
+        0.7,
+        Vec3::new(0.8, 0.3, 0.3),
+    )));
+    let mut cube = Cube::new(
+        Vec3::new(-2.2, -0.5, -1.8),
+        Vec3::new(-1.2, 0.5, -0.8),
+        Vec3::new(0.8, 0.6, 0.2),
+    );
+    cube.rotate_y(0.5);
+    world.add(Box::new(cube));
+    world.add(Box::new(Cylinder::new(
+        Vec3::new(1.6, -0.5, -1.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.4,
+        1.2,
+        Vec3::new(0.3, 0.3, 0.8),
+    )));
+    world.add(Box::new(Triangle::new(
+        Vec3::new(-0.6, -0.5, 0.6),
+        Vec3::new(0.6, -0.5, 0.6),
+        Vec3::new(0.0, -0.5, 1.4),
+        Vec3::new(0.2, 0.7, 0.2),
+    )));
+    let lights = vec![Light::new(Vec3::new(4.0, 5.0, 3.0), 0.8)];
+    let scene = Scene::new(world, lights, Box::new(Gradient::default()))
+        .with_ambient(Ambient::default());
+
+    let camera = camera_settings(Vec3::new(0.0, 1.2, 4.0), Vec3::new(0.0, 0.0, -0.8));
+    compare("shapes", &render_image(&scene, &camera, &settings(4)));
+}
+
+// Verre, découpe en damier, objet émissif et lobe de Blinn-Phong normalisé
+#[test]
+fn materials() {
+    let mut world = World::new();
+    world.add(Box::new(floor()));
+    world.add(Box::new(
+        Sphere::new(Vec3::new(-1.0, 0.1, -1.0), 0.6, Vec3::new(0.6, 0.9, 1.0))
+            .with_material(Material::glass(Vec3::new(0.6, 0.9, 1.0), 0.8)),
+    ));
+    world.add(Box::new(
+        Sphere::new(Vec3::new(0.6, 0.1, -1.4), 0.6, Vec3::new(0.9, 0.5, 0.1)).with_material(
+            Material::new(Vec3::new(0.9, 0.5, 0.1))
+                .with_alpha(AlphaMask::Checker { scale: 4.0, alpha: 0.0 }),
+        ),
+    ));
+    world.add(Box::new(
+        Sphere::new(Vec3::new(1.5, 0.9, 0.0), 0.25, Vec3::new(1.0, 1.0, 1.0))
+            .with_material(Material::emissive(Vec3::new(1.0, 0.8, 0.6), 4.0)),
+    ));
+    let lights = vec![Light::new(Vec3::new(-3.0, 4.0, 3.0), 0.6)];
+    let scene = Scene::new(world, lights, Box::new(Gradient::default()))
+        .with_ambient(Ambient::default())
+        .with_shading(Shading::blinn_phong(0.5, 64.0).normalized());
+
+    let camera = camera_settings(Vec3::new(0.0, 1.0, 3.5), Vec3::new(0.0, 0.0, -1.0));
+    compare("materials", &render_image(&scene, &camera, &settings(8)));
+}
+
+// Flou de mouvement et profondeur de champ
+#[test]
+fn motion_and_focus() {
+    let mut world = World::new();
+    world.add(Box::new(floor()));
+    world.add(Box::new(
+        Animated::new(Box::new(Sphere::new(
+            Vec3::new(-0.8, 0.0, -1.0),
+            0.5,
+            Vec3::new(1.0, 1.0, 0.0),
+        )))
+        .with_velocity(Vec3::new(6.0, 0.0, 0.0)),
+    ));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(1.2, 0.0, -4.0),
+        0.5,
+        Vec3::new(0.3, 0.8, 0.3),
+    )));
+    let lights = vec![Light::new(Vec3::new(3.0, 5.0, 3.0), 0.8)];
+    let scene = Scene::new(world, lights, Box::new(Gradient::default()))
+        .with_ambient(Ambient::default());
+
+    let mut camera = camera_settings(Vec3::new(0.0, 0.8, 3.0), Vec3::new(0.0, 0.0, -1.0))
+        .with_shutter(Shutter::new(0.0, 0.1));
+    camera.aperture = 0.15;
+    camera.focus_distance = Some(4.0);
+    compare("motion_and_focus", &render_image(&scene, &camera, &settings(16)));
+}
+
+// Ciel procédural, soleil et éclairage par l'environnement
+#[test]
+fn sky() {
+    let mut world = World::new();
+    world.add(Box::new(floor()));
+    world.add(Box::new(Sphere::new(
+        Vec3::new(0.0, 0.2, -1.0),
+        0.7,
+        Vec3::new(0.8, 0.8, 0.8),
+    )));
+    let sky = Sky::new(30.0, 120.0, 3.0).with_intensity(0.4);
+    let lights = vec![sky.sun_light(1.0)];
+    let scene = Scene::new(world, lights, Box::new(sky)).with_ambient(Ambient::default());
+
+    let camera = camera_settings(Vec3::new(0.0, 1.0, 3.5), Vec3::new(0.0, 0.2, -1.0));
+    compare("sky", &render_image(&scene, &camera, &settings(8)));
+}
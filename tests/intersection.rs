@@ -0,0 +1,536 @@
+// Propriétés des intersections rayon-objet, vérifiées sur des rayons tirés au hasard (graine fixe,
+// nombres de rt::sampler) : t dans ]t_min, t_max[, point sur la surface, normale unitaire et
+// extérieure, rayons qui manquent l'objet, rayons partis de l'intérieur et rayons rasants
+use rt::cube::*;
+use rt::cylindre::*;
+use rt::hittable::*;
+use rt::plane::*;
+use rt::ray::*;
+use rt::sampler::{hash, to_unit};
+use rt::sphere::*;
+use rt::triangle::*;
+use rt::vec3::*;
+
+const CASES: u64 = 2000;
+const EPSILON: f64 = 1e-6;
+const T_MIN: f64 = 0.001;
+
+// Suite de nombres dans [0, 1[, reproductible pour un nom de test donné
+struct Random {
+    seed: u64,
+    index: u64,
+}
+
+impl Random {
+    fn new(name: &str) -> Self {
+        let seed = name
+            .bytes()
+            .fold(0, |seed, byte| hash(&[seed, byte as u64]));
+        Random { seed, index: 0 }
+    }
+
+    fn next(&mut self) -> f64 {
+        self.index += 1;
+        to_unit(hash(&[self.seed, self.index]))
+    }
+
+    fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next()
+    }
+
+    fn unit_vector(&mut self) -> Vec3 {
+        let z = 1.0 - 2.0 * self.next();
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * std::f64::consts::PI * self.next();
+        Vec3::new(r * phi.cos(), r * phi.sin(), z)
+    }
+
+    fn point(&mut self, extent: f64) -> Vec3 {
+        Vec3::new(
+            self.range(-extent, extent),
+            self.range(-extent, extent),
+            self.range(-extent, extent),
+        )
+    }
+}
+
+fn color() -> Vec3 {
+    Vec3::new(0.5, 0.5, 0.5)
+}
+
+// Rayon qui part d'un point pris entre 0.5 et 20 de `target` et se dirige vers lui, du côté de la
+// surface indiqué par `side`
+fn ray_towards(random: &mut Random, target: &Vec3, side: &Vec3) -> Ray {
+    let distance = random.range(0.5, 20.0);
+    let mut offset = random.unit_vector();
+    if offset.dot(side) < 0.0 {
+        offset = -offset;
+    }
+    let origin = *target + offset * distance;
+    Ray::new(origin, (*target - origin).normalize())
+}
+
+// Vérifications communes à toutes les formes
+fn check_hit(hit: &HitRecord, ray: &Ray, t_min: f64, t_max: f64) {
+    assert!(
+        hit.t > t_min && hit.t < t_max,
+        "t = {} hors de ]{}, {}[",
+        hit.t,
+        t_min,
+        t_max
+    );
+    let expected = ray.point_at_parameter(hit.t);
+    assert!(
+        (hit.point - expected).length() < 1e-6,
+        "le point touché n'est pas sur le rayon"
+    );
+    assert!(
+        (hit.normal.length() - 1.0).abs() < EPSILON,
+        "normale non unitaire : {}",
+        hit.normal.length()
+    );
+}
+
+fn check_sphere_surface(sphere: &Sphere, hit: &HitRecord) {
+    let distance = (hit.point - sphere.center).length();
+    assert!(
+        (distance - sphere.radius).abs() < 1e-6,
+        "point à {} du centre",
+        distance
+    );
+    let outward = (hit.point - sphere.center) * (1.0 / sphere.radius);
+    assert!(
+        (hit.normal - outward).length() < 1e-6,
+        "normale non extérieure"
+    );
+}
+
+#[test]
+fn sphere_hits_from_outside() {
+    let mut random = Random::new("sphere_hits_from_outside");
+    for _ in 0..CASES {
+        let sphere = Sphere::new(random.point(5.0), random.range(0.1, 3.0), color());
+        let normal = random.unit_vector();
+        let target = sphere.center + normal * sphere.radius;
+        let ray = ray_towards(&mut random, &target, &normal);
+
+        let hit = sphere
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon vers la sphère sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        check_sphere_surface(&sphere, &hit);
+        assert!(
+            hit.normal.dot(&ray.direction) < 0.0,
+            "l'impact depuis l'extérieur fait face au rayon"
+        );
+
+        // Sans la portion du rayon qui contient l'impact, plus rien n'est touché
+        assert!(sphere.hit(&ray, T_MIN, hit.t * 0.999).is_none());
+    }
+}
+
+#[test]
+fn sphere_hits_from_inside() {
+    let mut random = Random::new("sphere_hits_from_inside");
+    for _ in 0..CASES {
+        let sphere = Sphere::new(random.point(5.0), random.range(0.1, 3.0), color());
+        let origin =
+            sphere.center + random.unit_vector() * (sphere.radius * random.range(0.0, 0.95));
+        let ray = Ray::new(origin, random.unit_vector());
+
+        let hit = sphere
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon intérieur sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        check_sphere_surface(&sphere, &hit);
+        assert!(
+            hit.normal.dot(&ray.direction) > 0.0,
+            "depuis l'intérieur, la normale extérieure suit le rayon"
+        );
+    }
+}
+
+#[test]
+fn sphere_misses_and_grazing_rays() {
+    let mut random = Random::new("sphere_misses_and_grazing_rays");
+    for _ in 0..CASES {
+        let sphere = Sphere::new(random.point(5.0), random.range(0.1, 3.0), color());
+        let direction = random.unit_vector();
+        // Direction perpendiculaire pour décaler le rayon de l'axe passant par le centre
+        let side = direction.cross(&random.unit_vector()).normalize();
+
+        // Rayon qui s'éloigne de la sphère
+        let outside = sphere.center + direction * (sphere.radius * random.range(1.1, 5.0));
+        assert!(sphere
+            .hit(&Ray::new(outside, direction), T_MIN, f64::INFINITY)
+            .is_none());
+
+        // Rayons tangents, juste à l'extérieur et juste à l'intérieur du bord
+        let start = sphere.center - direction * (sphere.radius * 3.0);
+        let grazing_out = Ray::new(start + side * (sphere.radius * 1.001), direction);
+        assert!(sphere.hit(&grazing_out, T_MIN, f64::INFINITY).is_none());
+        let grazing_in = Ray::new(start + side * (sphere.radius * 0.999), direction);
+        let hit = sphere
+            .hit(&grazing_in, T_MIN, f64::INFINITY)
+            .expect("rayon rasant intérieur sans impact");
+        check_hit(&hit, &grazing_in, T_MIN, f64::INFINITY);
+        check_sphere_surface(&sphere, &hit);
+    }
+}
+
+#[test]
+fn plane_hits_and_misses() {
+    let mut random = Random::new("plane_hits_and_misses");
+    for _ in 0..CASES {
+        let plane = Plane::new(random.point(5.0), random.unit_vector(), color());
+        let target =
+            plane.point + random.unit_vector().cross(&plane.normal) * random.range(0.0, 10.0);
+        let side = if random.next() < 0.5 {
+            plane.normal
+        } else {
+            -plane.normal
+        };
+        let ray = ray_towards(&mut random, &target, &side);
+
+        match plane.hit(&ray, T_MIN, f64::INFINITY) {
+            Some(hit) => {
+                check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+                assert!(
+                    (hit.point - plane.point).dot(&plane.normal).abs() < 1e-6,
+                    "point hors du plan"
+                );
+                assert!(
+                    (hit.normal - plane.normal).length() < EPSILON,
+                    "normale différente de celle du plan"
+                );
+                assert!(plane.hit(&ray, T_MIN, hit.t * 0.999).is_none());
+            }
+            // Seuls les rayons presque parallèles au plan peuvent le manquer
+            None => assert!(plane.normal.dot(&ray.direction).abs() < 1e-6),
+        }
+
+        // Rayon qui s'éloigne du plan, et rayon parallèle
+        let away = Ray::new(ray.origin, side);
+        assert!(plane.hit(&away, T_MIN, f64::INFINITY).is_none());
+        let parallel = Ray::new(
+            ray.origin,
+            random.unit_vector().cross(&plane.normal).normalize(),
+        );
+        assert!(plane.hit(&parallel, T_MIN, f64::INFINITY).is_none());
+    }
+}
+
+fn random_cylinder(random: &mut Random) -> Cylinder {
+    Cylinder::new(
+        random.point(5.0),
+        random.unit_vector(),
+        random.range(0.1, 2.0),
+        random.range(0.1, 4.0),
+        color(),
+    )
+}
+
+fn check_cylinder_surface(cylinder: &Cylinder, hit: &HitRecord) {
+    let along = cylinder.axis.dot(&(hit.point - cylinder.base));
+    assert!(
+        along >= -1e-6 && along <= cylinder.height + 1e-6,
+        "point au-delà des extrémités"
+    );
+    let radial = hit.point - (cylinder.base + cylinder.axis * along);
+    assert!(
+        (radial.length() - cylinder.radius).abs() < 1e-6,
+        "point à {} de l'axe",
+        radial.length()
+    );
+    assert!(
+        (hit.normal - radial.normalize()).length() < 1e-6,
+        "normale non extérieure"
+    );
+}
+
+#[test]
+fn cylinder_hits_from_outside() {
+    let mut random = Random::new("cylinder_hits_from_outside");
+    for _ in 0..CASES {
+        let cylinder = random_cylinder(&mut random);
+        let sample = cylinder
+            .sample_surface(random.next(), random.next())
+            .unwrap();
+        let ray = ray_towards(&mut random, &sample.point, &sample.normal);
+
+        // Le rayon peut rencontrer une autre partie du cylindre avant le point visé, jamais après
+        let hit = cylinder
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon vers le cylindre sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        check_cylinder_surface(&cylinder, &hit);
+        assert!(hit.t <= (sample.point - ray.origin).length() + 1e-6);
+    }
+}
+
+#[test]
+fn cylinder_hits_from_inside() {
+    let mut random = Random::new("cylinder_hits_from_inside");
+    for _ in 0..CASES {
+        let cylinder = random_cylinder(&mut random);
+        let sample = cylinder
+            .sample_surface(random.next(), random.next())
+            .unwrap();
+        // Point intérieur : entre l'axe et le point tiré sur la surface
+        let origin = sample.point - sample.normal * (cylinder.radius * random.range(0.05, 1.0));
+        // Direction qui reste dans la hauteur du cylindre : perpendiculaire à l'axe
+        let direction = random.unit_vector().cross(&cylinder.axis).normalize();
+        let ray = Ray::new(origin, direction);
+
+        let hit = cylinder
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon intérieur sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        check_cylinder_surface(&cylinder, &hit);
+        assert!(
+            hit.normal.dot(&ray.direction) > 0.0,
+            "depuis l'intérieur, la normale extérieure suit le rayon"
+        );
+    }
+}
+
+#[test]
+fn cylinder_misses_and_parallel_rays() {
+    let mut random = Random::new("cylinder_misses_and_parallel_rays");
+    for _ in 0..CASES {
+        let cylinder = random_cylinder(&mut random);
+        let sample = cylinder
+            .sample_surface(random.next(), random.next())
+            .unwrap();
+
+        // Rayon qui part de la surface vers l'extérieur
+        let away = Ray::new(sample.point, sample.normal);
+        assert!(cylinder.hit(&away, T_MIN, f64::INFINITY).is_none());
+
+        // Rayon parallèle à l'axe, dedans ou dehors : la surface latérale n'est pas coupée
+        let offset = sample.normal * (cylinder.radius * random.range(-2.0, 2.0));
+        let parallel = Ray::new(cylinder.base + offset - cylinder.axis, cylinder.axis);
+        assert!(cylinder.hit(&parallel, T_MIN, f64::INFINITY).is_none());
+
+        // Rayon perpendiculaire qui passe au-delà d'une extrémité
+        let beyond = cylinder.base + cylinder.axis * (cylinder.height * random.range(1.01, 2.0));
+        let start = beyond + sample.normal * (cylinder.radius * 3.0);
+        let ray = Ray::new(start, -sample.normal);
+        assert!(cylinder.hit(&ray, T_MIN, f64::INFINITY).is_none());
+    }
+}
+
+// Cube aligné sur les axes, avec ses coins pour les vérifications
+fn random_cube(random: &mut Random) -> (Cube, Vec3, Vec3) {
+    let min = random.point(5.0);
+    let size = Vec3::new(
+        random.range(0.1, 3.0),
+        random.range(0.1, 3.0),
+        random.range(0.1, 3.0),
+    );
+    let max = min + size;
+    (Cube::new(min, max, color()), min, max)
+}
+
+fn check_cube_surface(min: &Vec3, max: &Vec3, hit: &HitRecord) {
+    let p = hit.point;
+    let inside = |value: f64, low: f64, high: f64| value >= low - 1e-6 && value <= high + 1e-6;
+    assert!(
+        inside(p.x, min.x, max.x) && inside(p.y, min.y, max.y) && inside(p.z, min.z, max.z),
+        "point hors du cube"
+    );
+    // La normale est celle de la face sur laquelle se trouve le point
+    let n = hit.normal;
+    let face = if n.x < -0.5 {
+        p.x - min.x
+    } else if n.x > 0.5 {
+        p.x - max.x
+    } else if n.y < -0.5 {
+        p.y - min.y
+    } else if n.y > 0.5 {
+        p.y - max.y
+    } else if n.z < -0.5 {
+        p.z - min.z
+    } else {
+        p.z - max.z
+    };
+    assert!(
+        face.abs() < 1e-6,
+        "point à {} de la face de sa normale",
+        face
+    );
+    let axis_aligned = [n.x.abs(), n.y.abs(), n.z.abs()];
+    assert_eq!(
+        axis_aligned.iter().filter(|&&c| c == 1.0).count(),
+        1,
+        "normale non alignée sur un axe"
+    );
+}
+
+#[test]
+fn cube_hits_from_outside() {
+    let mut random = Random::new("cube_hits_from_outside");
+    for _ in 0..CASES {
+        let (cube, min, max) = random_cube(&mut random);
+        let sample = cube.sample_surface(random.next(), random.next()).unwrap();
+        let ray = ray_towards(&mut random, &sample.point, &sample.normal);
+
+        let hit = cube
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon vers le cube sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        check_cube_surface(&min, &max, &hit);
+        assert!(
+            hit.normal.dot(&ray.direction) < 0.0,
+            "l'impact depuis l'extérieur fait face au rayon"
+        );
+        assert!(hit.t <= (sample.point - ray.origin).length() + 1e-6);
+        assert!(cube.hit(&ray, T_MIN, hit.t * 0.999).is_none());
+    }
+}
+
+#[test]
+fn cube_hits_from_inside() {
+    let mut random = Random::new("cube_hits_from_inside");
+    for _ in 0..CASES {
+        let (cube, min, max) = random_cube(&mut random);
+        // Assez loin des faces pour que la sortie soit au-delà de t_min
+        let origin = Vec3::new(
+            random.range(min.x + 0.01, max.x - 0.01),
+            random.range(min.y + 0.01, max.y - 0.01),
+            random.range(min.z + 0.01, max.z - 0.01),
+        );
+        let ray = Ray::new(origin, random.unit_vector());
+
+        let hit = cube
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon intérieur sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        check_cube_surface(&min, &max, &hit);
+        assert!(
+            hit.normal.dot(&ray.direction) > 0.0,
+            "depuis l'intérieur, la normale extérieure suit le rayon"
+        );
+    }
+}
+
+#[test]
+fn cube_misses_and_grazing_rays() {
+    let mut random = Random::new("cube_misses_and_grazing_rays");
+    for _ in 0..CASES {
+        let (cube, min, max) = random_cube(&mut random);
+        let sample = cube.sample_surface(random.next(), random.next()).unwrap();
+
+        // Rayon qui part d'une face vers l'extérieur
+        let away = Ray::new(sample.point, sample.normal);
+        assert!(cube.hit(&away, T_MIN, f64::INFINITY).is_none());
+
+        // Rayon parallèle à une face, juste au-dessus d'elle
+        let above = sample.point + sample.normal * 1e-3;
+        let along = random.unit_vector().cross(&sample.normal).normalize();
+        let grazing = Ray::new(above - along * 10.0, along);
+        assert!(cube.hit(&grazing, T_MIN, f64::INFINITY).is_none());
+
+        // Rayon parallèle à une face, juste en dessous : il traverse le cube ou le manque sans valeur aberrante
+        let below = sample.point - sample.normal * 1e-3;
+        let grazing = Ray::new(below - along * 10.0, along);
+        if let Some(hit) = cube.hit(&grazing, T_MIN, f64::INFINITY) {
+            check_hit(&hit, &grazing, T_MIN, f64::INFINITY);
+            check_cube_surface(&min, &max, &hit);
+        }
+    }
+}
+
+// Rayons parallèles à une face et contenus dans son plan : la tranche de cet axe ne doit pas
+// donner 0 × ∞ (NaN), quel que soit le signe des composantes nulles de la direction
+#[test]
+fn cube_rays_in_face_planes() {
+    let mut random = Random::new("cube_rays_in_face_planes");
+    let vector = |c: [f64; 3]| Vec3::new(c[0], c[1], c[2]);
+    for _ in 0..CASES {
+        let (cube, min, max) = random_cube(&mut random);
+        let (low, high) = ([min.x, min.y, min.z], [max.x, max.y, max.z]);
+        // Axe de la face dont le plan contient le rayon, axe du rayon et axe restant
+        let face = (random.next() * 3.0) as usize;
+        let along = (face + 1 + (random.next() * 2.0) as usize) % 3;
+        let other = 3 - face - along;
+
+        let mut origin = [0.0; 3];
+        origin[face] = if random.next() < 0.5 {
+            low[face]
+        } else {
+            high[face]
+        };
+        origin[along] = low[along] - 1.0;
+        origin[other] = random.range(low[other], high[other]);
+        for zero in [0.0, -0.0] {
+            let mut direction = [zero; 3];
+            direction[along] = 1.0;
+            let ray = Ray::new(vector(origin), vector(direction));
+            let hit = cube
+                .hit(&ray, T_MIN, f64::INFINITY)
+                .expect("rayon dans le plan d'une face sans impact");
+            check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+            check_cube_surface(&min, &max, &hit);
+            assert!((hit.t - 1.0).abs() < EPSILON, "t = {} au lieu de 1", hit.t);
+        }
+
+        // Même plan, mais à côté de la face
+        origin[other] = high[other] + 0.5;
+        let mut direction = [0.0; 3];
+        direction[along] = 1.0;
+        let ray = Ray::new(vector(origin), vector(direction));
+        assert!(cube.hit(&ray, T_MIN, f64::INFINITY).is_none());
+    }
+}
+
+#[test]
+fn triangle_hits_and_misses() {
+    let mut random = Random::new("triangle_hits_and_misses");
+    for _ in 0..CASES {
+        let triangle = Triangle::new(
+            random.point(5.0),
+            random.point(5.0),
+            random.point(5.0),
+            color(),
+        );
+        let edge1 = triangle.v1 - triangle.v0;
+        let edge2 = triangle.v2 - triangle.v0;
+        let cross = edge1.cross(&edge2);
+        // Triangles presque dégénérés écartés
+        if cross.length() < 0.1 {
+            continue;
+        }
+        let normal = cross.normalize();
+
+        // Point intérieur tiré en coordonnées barycentriques, rayon depuis l'un ou l'autre côté
+        let (mut u, mut v) = (random.range(0.02, 0.98), random.range(0.02, 0.98));
+        if u + v > 0.98 {
+            (u, v) = (0.98 - u, 0.98 - v);
+        }
+        let target = triangle.v0 + edge1 * u + edge2 * v;
+        let side = if random.next() < 0.5 { normal } else { -normal };
+        let ray = ray_towards(&mut random, &target, &side);
+        if normal.dot(&ray.direction).abs() < 1e-3 {
+            continue;
+        }
+
+        let hit = triangle
+            .hit(&ray, T_MIN, f64::INFINITY)
+            .expect("rayon vers le triangle sans impact");
+        check_hit(&hit, &ray, T_MIN, f64::INFINITY);
+        assert!(
+            (hit.point - target).length() < 1e-6,
+            "impact ailleurs que sur le point visé"
+        );
+        assert!(
+            (hit.normal - normal).length() < EPSILON,
+            "normale différente de celle du triangle"
+        );
+
+        // Point en dehors du triangle, dans son plan
+        let outside = triangle.v0 + edge1 * (1.0 + u) + edge2 * v;
+        let ray = ray_towards(&mut random, &outside, &side);
+        assert!(triangle.hit(&ray, T_MIN, f64::INFINITY).is_none());
+    }
+}
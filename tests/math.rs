@@ -0,0 +1,431 @@
+// Propriétés des matrices, quaternions, bases orthonormées et des aides de Vec3, vérifiées sur des
+// valeurs tirées au hasard (graine fixe, nombres de rt::sampler)
+use rt::matrix::*;
+use rt::onb::*;
+use rt::quaternion::*;
+use rt::sampler::{hash, to_unit};
+use rt::vec3::*;
+
+const CASES: u64 = 2000;
+const TOLERANCE: f64 = 1e-9;
+
+// Suite de nombres dans [0, 1[, reproductible pour un nom de test donné
+struct Random {
+    seed: u64,
+    index: u64,
+}
+
+impl Random {
+    fn new(name: &str) -> Self {
+        let seed = name
+            .bytes()
+            .fold(0, |seed, byte| hash(&[seed, byte as u64]));
+        Random { seed, index: 0 }
+    }
+
+    fn next(&mut self) -> f64 {
+        self.index += 1;
+        to_unit(hash(&[self.seed, self.index]))
+    }
+
+    fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next()
+    }
+
+    fn unit_vector(&mut self) -> Vec3 {
+        let z = 1.0 - 2.0 * self.next();
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        let phi = 2.0 * std::f64::consts::PI * self.next();
+        Vec3::new(r * phi.cos(), r * phi.sin(), z)
+    }
+
+    fn vector(&mut self, extent: f64) -> Vec3 {
+        Vec3::new(
+            self.range(-extent, extent),
+            self.range(-extent, extent),
+            self.range(-extent, extent),
+        )
+    }
+
+    fn angle(&mut self) -> f64 {
+        self.range(-std::f64::consts::PI, std::f64::consts::PI)
+    }
+
+    // Échelle non uniforme, éventuellement avec symétrie, jamais nulle
+    fn scale(&mut self) -> Vec3 {
+        let mut factor = || {
+            let magnitude = self.range(0.2, 5.0);
+            if self.next() < 0.5 {
+                -magnitude
+            } else {
+                magnitude
+            }
+        };
+        Vec3::new(factor(), factor(), factor())
+    }
+
+    fn quaternion(&mut self) -> Quat {
+        let axis = self.unit_vector();
+        let angle = self.angle();
+        Quat::from_axis_angle(&axis, angle)
+    }
+
+    // Transformation affine quelconque : translation, rotation et échelle
+    fn transform(&mut self) -> Mat4 {
+        let axis = self.unit_vector();
+        let angle = self.angle();
+        Mat4::translation(&self.vector(10.0))
+            * Mat4::rotation(&axis, angle)
+            * Mat4::scale(&self.scale())
+    }
+}
+
+fn assert_close(a: &Vec3, b: &Vec3, context: &str) {
+    assert!(
+        a.approx_eq(b, TOLERANCE),
+        "{} : {:?} != {:?}",
+        context,
+        a,
+        b
+    );
+}
+
+#[test]
+fn mat3_inverse_round_trip() {
+    let mut random = Random::new("mat3_inverse_round_trip");
+    for _ in 0..CASES {
+        let axis = random.unit_vector();
+        let m = Mat3::rotation(&axis, random.angle()) * Mat3::scale(&random.scale());
+        let inverse = m.inverse().expect("matrice inversible");
+        assert!(
+            (m * inverse).approx_eq(&Mat3::identity(), TOLERANCE),
+            "{:?}",
+            m
+        );
+        assert!(
+            (inverse * m).approx_eq(&Mat3::identity(), TOLERANCE),
+            "{:?}",
+            m
+        );
+        let v = random.vector(10.0);
+        assert_close(&(inverse * (m * v)), &v, "m⁻¹ m v");
+    }
+    // Une colonne nulle ou deux colonnes égales : pas d'inverse
+    assert!(Mat3::scale(&Vec3::new(1.0, 0.0, 2.0)).inverse().is_none());
+    let column = Vec3::new(1.0, 2.0, 3.0);
+    assert!(Mat3::from_cols(&column, &column, &Vec3::new(0.0, 0.0, 1.0))
+        .inverse()
+        .is_none());
+}
+
+#[test]
+fn mat4_inverse_round_trip() {
+    let mut random = Random::new("mat4_inverse_round_trip");
+    for _ in 0..CASES {
+        let m = random.transform();
+        let inverse = m.inverse().expect("matrice inversible");
+        assert!(
+            (m * inverse).approx_eq(&Mat4::identity(), TOLERANCE),
+            "{:?}",
+            m
+        );
+        assert!(
+            (inverse * m).approx_eq(&Mat4::identity(), TOLERANCE),
+            "{:?}",
+            m
+        );
+        let p = random.vector(10.0);
+        assert_close(
+            &inverse.transform_point(&m.transform_point(&p)),
+            &p,
+            "point",
+        );
+        assert_close(
+            &inverse.transform_vector(&m.transform_vector(&p)),
+            &p,
+            "vecteur",
+        );
+    }
+}
+
+// Diagonale nulle : l'élimination doit échanger les lignes pour trouver un pivot
+#[test]
+fn mat4_inverse_needs_pivoting() {
+    let mut random = Random::new("mat4_inverse_needs_pivoting");
+    for _ in 0..CASES {
+        let (a, b, c, d) = (
+            random.range(0.5, 3.0),
+            random.range(0.5, 3.0),
+            random.range(0.5, 3.0),
+            random.range(0.5, 3.0),
+        );
+        let m = Mat4::new([
+            [0.0, a, 0.0, 0.0],
+            [b, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, c],
+            [0.0, 0.0, d, 0.0],
+        ]);
+        let inverse = m.inverse().expect("matrice inversible");
+        assert!(
+            (m * inverse).approx_eq(&Mat4::identity(), TOLERANCE),
+            "{:?}",
+            m
+        );
+    }
+    let mut singular = Mat4::identity();
+    singular.rows[2] = singular.rows[1];
+    assert!(singular.inverse().is_none());
+}
+
+// Une normale transformée reste perpendiculaire aux tangentes transformées, même avec une échelle
+// non uniforme
+#[test]
+fn transform_normal_stays_perpendicular() {
+    let mut random = Random::new("transform_normal_stays_perpendicular");
+    for _ in 0..CASES {
+        let m = random.transform();
+        let normal = random.unit_vector();
+        let tangent = normal.cross(&random.unit_vector());
+        if tangent.length() < 1e-3 {
+            continue;
+        }
+        let transformed = m.transform_normal(&normal).expect("matrice inversible");
+        let tangent = m.transform_vector(&tangent).normalize();
+        assert!((transformed.length() - 1.0).abs() < TOLERANCE);
+        assert!(
+            transformed.dot(&tangent).abs() < 1e-9,
+            "normale {:?} et tangente {:?} non perpendiculaires",
+            transformed,
+            tangent
+        );
+    }
+}
+
+#[test]
+fn rotation_y_matches_vec3_rotate_y() {
+    let mut random = Random::new("rotation_y_matches_vec3_rotate_y");
+    for _ in 0..CASES {
+        let angle = random.angle();
+        let v = random.vector(10.0);
+        assert_close(
+            &(Mat3::rotation_y(angle) * v),
+            &v.rotate_y(angle),
+            "rotation_y",
+        );
+        // Les rotations conservent les longueurs et ont un déterminant 1
+        let m = Mat3::rotation(&random.unit_vector(), angle);
+        assert!((m.determinant() - 1.0).abs() < TOLERANCE);
+        assert!(((m * v).length() - v.length()).abs() < 1e-9 * v.length().max(1.0));
+    }
+}
+
+#[test]
+fn quaternion_matches_matrix() {
+    let mut random = Random::new("quaternion_matches_matrix");
+    for _ in 0..CASES {
+        let axis = random.unit_vector();
+        let angle = random.angle();
+        let q = Quat::from_axis_angle(&axis, angle);
+        let m = Mat3::rotation(&axis, angle);
+        let v = random.vector(10.0);
+        assert_close(&q.rotate(&v), &(m * v), "q.rotate");
+        assert!(q.to_mat3().approx_eq(&m, TOLERANCE));
+        // Le conjugué annule la rotation
+        assert_close(&q.conjugate().rotate(&q.rotate(&v)), &v, "conjugué");
+    }
+}
+
+// Produit de Hamilton : (a * b) applique b puis a
+#[test]
+fn quaternion_product_composes_rotations() {
+    let mut random = Random::new("quaternion_product_composes_rotations");
+    for _ in 0..CASES {
+        let (a, b) = (random.quaternion(), random.quaternion());
+        let v = random.vector(10.0);
+        assert_close(&(a * b).rotate(&v), &a.rotate(&b.rotate(&v)), "a * b");
+        assert!(((a * b).length() - 1.0).abs() < TOLERANCE);
+        assert!((a * Quat::identity()).approx_eq(&a, TOLERANCE));
+        assert!((a * a.conjugate()).approx_eq(&Quat::identity(), TOLERANCE));
+    }
+}
+
+#[test]
+fn slerp_endpoints_and_shortest_path() {
+    let mut random = Random::new("slerp_endpoints_and_shortest_path");
+    for _ in 0..CASES {
+        let (a, b) = (random.quaternion(), random.quaternion());
+        assert!(a.slerp(&b, 0.0).approx_eq(&a, TOLERANCE));
+        assert!(a.slerp(&b, 1.0).approx_eq(&b, TOLERANCE));
+
+        // -b est la même rotation que b : l'interpolation doit être identique (arc le plus court)
+        let negated = Quat::new(-b.w, -b.x, -b.y, -b.z);
+        let t = random.next();
+        let (direct, flipped) = (a.slerp(&b, t), a.slerp(&negated, t));
+        assert!(
+            direct.approx_eq(&flipped, TOLERANCE),
+            "{:?} != {:?}",
+            direct,
+            flipped
+        );
+        assert!((direct.length() - 1.0).abs() < TOLERANCE);
+
+        // Vitesse angulaire constante : le milieu est à égale distance des deux extrémités
+        let middle = a.slerp(&b, 0.5);
+        assert!((middle.dot(&a).abs() - middle.dot(&b).abs()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn onb_is_orthonormal_and_direct() {
+    let mut random = Random::new("onb_is_orthonormal_and_direct");
+    // Directions au hasard, puis autour du pôle -z où la construction change de signe
+    let mut directions = (0..CASES)
+        .map(|_| random.unit_vector())
+        .collect::<Vec<Vec3>>();
+    for _ in 0..CASES / 4 {
+        let offset = random.vector(1e-6);
+        directions.push(Vec3::new(offset.x, offset.y, -1.0).normalize());
+        directions.push(Vec3::new(offset.x, offset.y, 1.0).normalize());
+    }
+    directions.push(Vec3::new(0.0, 0.0, -1.0));
+    directions.push(Vec3::new(0.0, 0.0, 1.0));
+
+    for w in directions {
+        let basis = Onb::from_w(&w);
+        for (axis, name) in [(basis.u, "u"), (basis.v, "v")] {
+            assert!(
+                (axis.length() - 1.0).abs() < 1e-9,
+                "{} non unitaire pour {:?}",
+                name,
+                w
+            );
+            assert!(
+                axis.dot(&w).abs() < 1e-9,
+                "{} non perpendiculaire à {:?}",
+                name,
+                w
+            );
+        }
+        assert!(basis.u.dot(&basis.v).abs() < 1e-9);
+        assert_close(&basis.u.cross(&basis.v), &w, "u × v");
+        let v = random.vector(10.0);
+        assert_close(&basis.to_world(&basis.to_local(&v)), &v, "aller-retour");
+    }
+}
+
+#[test]
+fn reflect_and_refract() {
+    let mut random = Random::new("reflect_and_refract");
+    for _ in 0..CASES {
+        let normal = random.unit_vector();
+        let mut direction = random.unit_vector();
+        if direction.dot(&normal) > 0.0 {
+            direction = -direction;
+        }
+        let reflected = direction.reflect(&normal);
+        assert!((reflected.length() - 1.0).abs() < TOLERANCE);
+        assert!((reflected.dot(&normal) + direction.dot(&normal)).abs() < TOLERANCE);
+        assert_close(&reflected.reflect(&normal), &direction, "double réflexion");
+
+        // Loi de Snell : sin θt = eta sin θi, ou réflexion totale au-delà de l'angle critique
+        let eta = random.range(0.3, 2.5);
+        let cos_i = -direction.dot(&normal);
+        let sin_i = (1.0 - cos_i * cos_i).max(0.0).sqrt();
+        match direction.refract(&normal, eta) {
+            Some(refracted) => {
+                assert!(eta * sin_i <= 1.0 + TOLERANCE);
+                assert!((refracted.length() - 1.0).abs() < 1e-9);
+                assert!(
+                    refracted.dot(&normal) <= 0.0,
+                    "le rayon réfracté doit traverser"
+                );
+                let sin_t = refracted.cross(&normal).length();
+                assert!(
+                    (sin_t - eta * sin_i).abs() < 1e-9,
+                    "sin θt = {}, attendu {}",
+                    sin_t,
+                    eta * sin_i
+                );
+            }
+            None => assert!(eta * sin_i > 1.0 - TOLERANCE, "réflexion totale à tort"),
+        }
+        // Même indice des deux côtés : la direction ne change pas
+        assert_close(
+            &direction.refract(&normal, 1.0).unwrap(),
+            &direction,
+            "eta = 1",
+        );
+    }
+}
+
+// Les opérateurs donnent les mêmes résultats que les méthodes
+#[test]
+fn operators_match_methods() {
+    let mut random = Random::new("operators_match_methods");
+    for _ in 0..CASES {
+        let (a, b) = (random.vector(10.0), random.vector(10.0));
+        let s = random.range(-5.0, 5.0);
+        assert_eq!(a + b, a.add(&b));
+        assert_eq!(a - b, a.sub(&b));
+        assert_eq!(a * s, a.mul(s));
+        assert_eq!(s * a, a.mul(s));
+        assert_eq!(a * b, a.mul_vec(&b));
+        assert_eq!(-a, a.mul(-1.0));
+        assert_close(&(a / 4.0), &a.mul(0.25), "division");
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 2.0;
+        c /= 2.0;
+        assert_close(&c, &b, "affectations composées");
+        assert_close(
+            &[a, b, a].into_iter().sum::<Vec3>(),
+            &(a * 2.0 + b),
+            "somme",
+        );
+    }
+}
+
+// Point3 et Color se comportent comme le Vec3 qu'ils enveloppent
+#[test]
+fn newtypes_match_vectors() {
+    let mut random = Random::new("newtypes_match_vectors");
+    for _ in 0..CASES {
+        let (a, b, offset) = (
+            random.vector(10.0),
+            random.vector(10.0),
+            random.vector(10.0),
+        );
+        let s = random.range(-5.0, 5.0);
+
+        let (p, q) = (Point3::from(a), Point3::from(b));
+        assert_eq!(p - q, a - b);
+        assert_eq!(Vec3::from(p + offset), a + offset);
+        assert_eq!(Vec3::from(p - offset), a - offset);
+        assert_eq!(p.dot(&b), a.dot(&b));
+        assert_eq!(p.distance(&q), (a - b).length());
+        assert_eq!(p.lerp(&q, 0.0), p);
+        assert_close(&p.lerp(&q, 1.0), &b, "lerp");
+        let mut moved = p;
+        moved += offset;
+        assert_eq!(moved, p + offset);
+
+        let (c, d) = (Color::from(a), Color::from(b));
+        assert_eq!(Vec3::from(c + d), a + b);
+        assert_eq!(Vec3::from(c * d), a * b);
+        assert_eq!(Vec3::from(c * s), a * s);
+        assert_eq!(Vec3::from(c / 4.0), a / 4.0);
+        assert_eq!(c.luminance(), a.luminance());
+        let mut e = c;
+        e += d;
+        e *= d;
+        e *= s;
+        assert_eq!(Vec3::from(e), (a + b) * b * s);
+        assert_eq!(
+            Vec3::from([c, d, c].into_iter().sum::<Color>()),
+            [a, b, a].into_iter().sum::<Vec3>()
+        );
+    }
+    assert!(Color::black().is_black() && !Color::white().is_black());
+    assert_eq!(Point3::origin(), Point3::new(0.0, 0.0, 0.0));
+}
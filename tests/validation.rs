@@ -0,0 +1,461 @@
+// Constructeurs validants (try_new), Hittable::validate et contains, et rapport de Scene::validate :
+// chaque variante de GeometryError et de SceneIssue est provoquée au moins une fois
+use rt::animated::*;
+use rt::background::*;
+use rt::camera::*;
+use rt::cube::*;
+use rt::cylindre::*;
+use rt::environment::*;
+use rt::hittable::*;
+use rt::light::*;
+use rt::plane::*;
+use rt::quaternion::*;
+use rt::scene::*;
+use rt::sphere::*;
+use rt::triangle::*;
+use rt::validation::*;
+use rt::vec3::*;
+use rt::world::*;
+use std::io;
+
+fn color() -> Vec3 {
+    Vec3::new(0.5, 0.5, 0.5)
+}
+
+fn origin() -> Vec3 {
+    Vec3::new(0.0, 0.0, 0.0)
+}
+
+fn up() -> Vec3 {
+    Vec3::new(0.0, 1.0, 0.0)
+}
+
+fn nan() -> Vec3 {
+    Vec3::new(f64::NAN, 0.0, 0.0)
+}
+
+// Caméra en z = 5 qui regarde l'origine
+fn camera(projection: Projection) -> CameraSettings {
+    CameraSettings::new(Vec3::new(0.0, 0.0, 5.0), origin(), up(), 1.0, projection)
+}
+
+fn perspective() -> CameraSettings {
+    camera(Projection::Perspective { vfov: 60.0 })
+}
+
+fn scene(objects: Vec<Box<dyn Hittable>>, lights: Vec<Light>) -> Scene {
+    let mut world = World::new();
+    for object in objects {
+        world.add(object);
+    }
+    Scene::new(world, lights, Box::new(Gradient::default()))
+}
+
+#[test]
+fn normalize_zero_vector() {
+    assert_eq!(origin().try_normalize(), Err(GeometryError::ZeroVector));
+    assert_eq!(
+        Vec3::new(1e-20, 0.0, 0.0).try_normalize(),
+        Err(GeometryError::ZeroVector)
+    );
+    assert_eq!(nan().try_normalize(), Err(GeometryError::ZeroVector));
+    assert_eq!(
+        Vec3::new(f64::INFINITY, 0.0, 0.0).try_normalize(),
+        Err(GeometryError::ZeroVector)
+    );
+    assert_eq!(
+        Vec3::new(0.0, 3.0, 4.0).try_normalize(),
+        Ok(Vec3::new(0.0, 0.6, 0.8))
+    );
+
+    // normalize ne produit plus de NaN pour un vecteur ou un quaternion nul
+    let normalized = origin().normalize();
+    assert!(normalized.is_finite());
+    assert_eq!(normalized, origin());
+    let zero = Quat::new(0.0, 0.0, 0.0, 0.0);
+    assert_eq!(zero.normalize(), zero);
+}
+
+#[test]
+fn geometry_errors_convert_to_io_errors() {
+    let error = io::Error::from(Sphere::try_new(origin(), 0.0, color()).err().unwrap());
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(error.to_string().contains("rayon"));
+}
+
+#[test]
+fn sphere_try_new() {
+    assert!(Sphere::try_new(origin(), 1.0, color()).is_ok());
+    assert_eq!(
+        Sphere::try_new(nan(), 1.0, color()).err(),
+        Some(GeometryError::NotFinite { field: "centre" })
+    );
+    for radius in [0.0, -1.0, f64::INFINITY] {
+        assert_eq!(
+            Sphere::try_new(origin(), radius, color()).err(),
+            Some(GeometryError::NotPositive {
+                field: "rayon",
+                value: radius
+            })
+        );
+    }
+    // NaN n'est égal à rien : on vérifie la variante et le champ
+    assert!(matches!(
+        Sphere::try_new(origin(), f64::NAN, color()),
+        Err(GeometryError::NotPositive { field: "rayon", value }) if value.is_nan()
+    ));
+}
+
+#[test]
+fn plane_try_new() {
+    let plane = Plane::try_new(origin(), Vec3::new(0.0, 2.0, 0.0), color()).unwrap();
+    assert_eq!(plane.normal, up());
+    assert_eq!(
+        Plane::try_new(nan(), up(), color()).err(),
+        Some(GeometryError::NotFinite { field: "point" })
+    );
+    assert_eq!(
+        Plane::try_new(origin(), origin(), color()).err(),
+        Some(GeometryError::ZeroAxis { field: "normale" })
+    );
+    assert_eq!(
+        Plane::try_new(origin(), nan(), color()).err(),
+        Some(GeometryError::ZeroAxis { field: "normale" })
+    );
+}
+
+#[test]
+fn cylinder_try_new() {
+    let cylinder =
+        Cylinder::try_new(origin(), Vec3::new(0.0, 0.0, 3.0), 1.0, 2.0, color()).unwrap();
+    assert_eq!(cylinder.axis, Vec3::new(0.0, 0.0, 1.0));
+    assert_eq!(
+        Cylinder::try_new(nan(), up(), 1.0, 2.0, color()).err(),
+        Some(GeometryError::NotFinite { field: "base" })
+    );
+    assert_eq!(
+        Cylinder::try_new(origin(), origin(), 1.0, 2.0, color()).err(),
+        Some(GeometryError::ZeroAxis { field: "axe" })
+    );
+    assert_eq!(
+        Cylinder::try_new(origin(), up(), -0.5, 2.0, color()).err(),
+        Some(GeometryError::NotPositive {
+            field: "rayon",
+            value: -0.5
+        })
+    );
+    assert_eq!(
+        Cylinder::try_new(origin(), up(), 1.0, 0.0, color()).err(),
+        Some(GeometryError::NotPositive {
+            field: "hauteur",
+            value: 0.0
+        })
+    );
+}
+
+#[test]
+fn cube_try_new() {
+    let one = Vec3::new(1.0, 1.0, 1.0);
+    assert!(Cube::try_new(origin(), one, color()).is_ok());
+    assert_eq!(
+        Cube::try_new(Vec3::new(f64::NEG_INFINITY, 0.0, 0.0), one, color()).err(),
+        Some(GeometryError::NotFinite { field: "min" })
+    );
+    assert_eq!(
+        Cube::try_new(origin(), nan(), color()).err(),
+        Some(GeometryError::NotFinite { field: "max" })
+    );
+    // Inversé sur un axe, puis aplati
+    assert_eq!(
+        Cube::try_new(one, origin(), color()).err(),
+        Some(GeometryError::EmptyBox)
+    );
+    assert_eq!(
+        Cube::try_new(origin(), Vec3::new(1.0, 0.0, 1.0), color()).err(),
+        Some(GeometryError::EmptyBox)
+    );
+}
+
+#[test]
+fn triangle_try_new() {
+    let (a, b, c) = (origin(), Vec3::new(1.0, 0.0, 0.0), up());
+    assert!(Triangle::try_new(a, b, c, color()).is_ok());
+    assert_eq!(
+        Triangle::try_new(a, nan(), c, color()).err(),
+        Some(GeometryError::NotFinite { field: "v1" })
+    );
+    // Sommets alignés, puis confondus
+    assert_eq!(
+        Triangle::try_new(a, b, Vec3::new(2.0, 0.0, 0.0), color()).err(),
+        Some(GeometryError::DegenerateTriangle)
+    );
+    assert_eq!(
+        Triangle::try_new(a, a, c, color()).err(),
+        Some(GeometryError::DegenerateTriangle)
+    );
+}
+
+// Les objets construits sans vérification (new) sont repris par Hittable::validate
+#[test]
+fn environment_map_rejects_bad_sizes() {
+    let pixel = Vec3::new(1.0, 1.0, 1.0);
+    assert!(EnvironmentMap::new(2, 1, vec![pixel; 2]).is_ok());
+    assert!(EnvironmentMap::new(0, 1, Vec::new()).is_err());
+    assert!(EnvironmentMap::new(2, 0, Vec::new()).is_err());
+    assert!(EnvironmentMap::new(2, 2, vec![pixel; 3]).is_err());
+}
+
+#[test]
+fn validate_objects_built_with_new() {
+    assert_eq!(
+        Cylinder::new(origin(), origin(), 1.0, 1.0, color()).validate(),
+        Err(GeometryError::ZeroAxis { field: "axe" })
+    );
+    assert_eq!(
+        Plane::new(origin(), origin(), color()).validate(),
+        Err(GeometryError::ZeroAxis { field: "normale" })
+    );
+    assert_eq!(
+        Sphere::new(origin(), -2.0, color()).validate(),
+        Err(GeometryError::NotPositive {
+            field: "rayon",
+            value: -2.0
+        })
+    );
+    // Animated délègue à l'objet animé
+    let animated = Animated::new(Box::new(Sphere::new(origin(), -2.0, color())));
+    assert_eq!(
+        animated.validate(),
+        Err(GeometryError::NotPositive {
+            field: "rayon",
+            value: -2.0
+        })
+    );
+    assert!(Animated::new(Box::new(Sphere::new(origin(), 1.0, color())))
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn contains_points() {
+    let sphere = Sphere::new(origin(), 1.0, color());
+    assert!(sphere.contains(&Vec3::new(0.5, 0.0, 0.0)));
+    assert!(!sphere.contains(&Vec3::new(1.5, 0.0, 0.0)));
+
+    let cube = Cube::new(origin(), Vec3::new(1.0, 1.0, 1.0), color());
+    assert!(cube.contains(&Vec3::new(0.5, 0.5, 0.5)));
+    assert!(!cube.contains(&Vec3::new(0.5, 1.5, 0.5)));
+
+    let cylinder = Cylinder::new(origin(), up(), 1.0, 2.0, color());
+    assert!(cylinder.contains(&Vec3::new(0.5, 1.0, 0.0)));
+    assert!(!cylinder.contains(&Vec3::new(0.5, 2.5, 0.0)));
+    assert!(!cylinder.contains(&Vec3::new(1.5, 1.0, 0.0)));
+
+    // Surfaces ouvertes : rien n'est à l'intérieur, de part et d'autre de la normale
+    let plane = Plane::new(origin(), up(), color());
+    assert!(!plane.contains(&Vec3::new(0.0, -1.0, 0.0)));
+    assert!(!plane.contains(&Vec3::new(0.0, 1.0, 0.0)));
+    let triangle = Triangle::new(origin(), Vec3::new(1.0, 0.0, 0.0), up(), color());
+    assert!(!triangle.contains(&Vec3::new(0.2, 0.2, -0.1)));
+
+    // Objet animé : à sa position de l'instant courant
+    let mut animated = Animated::new(Box::new(Sphere::new(origin(), 1.0, color())))
+        .with_velocity(Vec3::new(10.0, 0.0, 0.0));
+    animated.set_time(1.0);
+    assert!(animated.contains(&Vec3::new(10.0, 0.0, 0.0)));
+    assert!(!animated.contains(&origin()));
+}
+
+#[test]
+fn valid_scene_has_no_issues() {
+    let scene = scene(
+        vec![
+            Box::new(Plane::new(Vec3::new(0.0, -1.0, 0.0), up(), color())),
+            Box::new(Sphere::new(origin(), 1.0, color())),
+        ],
+        vec![
+            Light::new(Vec3::new(3.0, 4.0, 2.0), 1.0),
+            Light::directional(Vec3::new(1.0, 1.0, 0.0), 1.0, color()),
+        ],
+    );
+    assert_eq!(scene.validate(&perspective()), Vec::new());
+}
+
+#[test]
+fn scene_reports_degenerate_objects() {
+    let scene = scene(
+        vec![
+            Box::new(Sphere::new(origin(), 1.0, color())),
+            Box::new(Cylinder::new(origin(), origin(), 1.0, 1.0, color())),
+            Box::new(Triangle::new(origin(), origin(), up(), color())),
+        ],
+        vec![],
+    );
+    assert_eq!(
+        scene.validate(&perspective()),
+        vec![
+            SceneIssue::DegenerateObject {
+                object: 1,
+                error: GeometryError::ZeroAxis { field: "axe" }
+            },
+            SceneIssue::DegenerateObject {
+                object: 2,
+                error: GeometryError::DegenerateTriangle
+            },
+        ]
+    );
+}
+
+#[test]
+fn scene_reports_invalid_lights() {
+    let mut nan_intensity = Light::new(Vec3::new(0.0, 5.0, 0.0), 1.0);
+    nan_intensity.intensity = f64::NAN;
+    let mut zero_direction = Light::directional(up(), 1.0, color());
+    zero_direction.direction = Some(origin());
+    let scene = scene(
+        vec![],
+        vec![Light::new(nan(), 1.0), nan_intensity, zero_direction],
+    );
+    assert_eq!(
+        scene.validate(&perspective()),
+        vec![
+            SceneIssue::InvalidLight {
+                light: 0,
+                error: GeometryError::NotFinite { field: "position" }
+            },
+            SceneIssue::InvalidLight {
+                light: 1,
+                error: GeometryError::NotFinite {
+                    field: "intensité"
+                }
+            },
+            SceneIssue::InvalidLight {
+                light: 2,
+                error: GeometryError::ZeroAxis { field: "direction" }
+            },
+        ]
+    );
+}
+
+#[test]
+fn scene_reports_objects_behind_camera() {
+    let objects = || -> Vec<Box<dyn Hittable>> {
+        vec![
+            Box::new(Sphere::new(origin(), 1.0, color())),
+            // Derrière la caméra (z = 5, regard vers -z)
+            Box::new(Sphere::new(Vec3::new(0.0, 0.0, 10.0), 1.0, color())),
+            // À cheval sur le plan de la caméra : une partie reste visible
+            Box::new(Sphere::new(Vec3::new(0.0, 0.0, 5.5), 1.0, color())),
+            // Un plan infini n'a pas de boîte, il n'est jamais signalé
+            Box::new(Plane::new(Vec3::new(0.0, 0.0, 20.0), up(), color())),
+        ]
+    };
+    assert_eq!(
+        scene(objects(), vec![]).validate(&perspective()),
+        vec![SceneIssue::BehindCamera { object: 1 }]
+    );
+    assert_eq!(
+        scene(objects(), vec![]).validate(&camera(Projection::Orthographic { view_height: 4.0 })),
+        vec![SceneIssue::BehindCamera { object: 1 }]
+    );
+    // Les projections panoramiques voient derrière elles
+    assert_eq!(
+        scene(objects(), vec![]).validate(&camera(Projection::Equirectangular)),
+        Vec::new()
+    );
+}
+
+#[test]
+fn scene_reports_lights_inside_geometry() {
+    let scene = scene(
+        vec![
+            Box::new(Sphere::new(origin(), 1.0, color())),
+            Box::new(Cube::new(
+                Vec3::new(2.0, -1.0, -1.0),
+                Vec3::new(4.0, 1.0, 1.0),
+                color(),
+            )),
+            Box::new(Cylinder::new(
+                Vec3::new(-3.0, -1.0, 0.0),
+                up(),
+                0.5,
+                2.0,
+                color(),
+            )),
+        ],
+        vec![
+            Light::new(Vec3::new(0.2, 0.0, 0.0), 1.0),
+            Light::new(Vec3::new(3.0, 0.0, 0.0), 1.0),
+            Light::new(Vec3::new(-3.0, 0.0, 0.0), 1.0),
+            Light::new(Vec3::new(0.0, 5.0, 0.0), 1.0),
+        ],
+    );
+    assert_eq!(
+        scene.validate(&perspective()),
+        vec![
+            SceneIssue::LightInside {
+                light: 0,
+                object: 0
+            },
+            SceneIssue::LightInside {
+                light: 1,
+                object: 1
+            },
+            SceneIssue::LightInside {
+                light: 2,
+                object: 2
+            },
+        ]
+    );
+}
+
+// Une lumière derrière un plan (du côté opposé à sa normale) n'est pas enfermée : le plan ne délimite
+// pas de volume. Même chose pour une lumière directionnelle, qui n'a pas de position
+#[test]
+fn light_behind_plane_is_not_inside() {
+    let mut directional = Light::directional(up(), 1.0, color());
+    directional.position = origin();
+    let scene = scene(
+        vec![
+            Box::new(Plane::new(Vec3::new(0.0, -1.0, 0.0), up(), color())),
+            Box::new(Sphere::new(origin(), 1.0, color())),
+        ],
+        vec![Light::new(Vec3::new(0.0, -3.0, 0.0), 1.0), directional],
+    );
+    assert_eq!(scene.validate(&perspective()), Vec::new());
+}
+
+// Les objets animés sont testés à leur position de l'instant courant de la scène
+#[test]
+fn scene_checks_animated_objects_at_current_time() {
+    let animated = Animated::new(Box::new(Sphere::new(origin(), 1.0, color())))
+        .with_velocity(Vec3::new(-3.0, 0.0, 0.0));
+    let mut scene = scene(
+        vec![Box::new(animated)],
+        vec![Light::new(Vec3::new(-3.0, 0.0, 0.0), 1.0)],
+    );
+    assert_eq!(scene.validate(&perspective()), Vec::new());
+    scene.set_time(1.0);
+    assert_eq!(
+        scene.validate(&perspective()),
+        vec![SceneIssue::LightInside {
+            light: 0,
+            object: 0
+        }]
+    );
+}
+
+#[test]
+fn issues_display_their_cause() {
+    let issue = SceneIssue::DegenerateObject {
+        object: 3,
+        error: GeometryError::NotPositive {
+            field: "rayon",
+            value: -1.0,
+        },
+    };
+    let message = issue.to_string();
+    assert!(message.contains("objet 3") && message.contains("rayon") && message.contains("-1"));
+    let error: Box<dyn std::error::Error> = Box::new(GeometryError::ZeroAxis { field: "axe" });
+    assert!(error.to_string().contains("axe"));
+}